@@ -0,0 +1,10 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use puzzle_utils::{maze_from_ascii, maze_to_ascii};
+
+fuzz_target!(|text: &str| {
+    if let Ok((width, height, grid)) = maze_from_ascii(text) {
+        let _ = maze_to_ascii(width, height, &grid);
+    }
+});
@@ -0,0 +1,16 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use puzzle_utils::{parse_nonogram_rules, validate_nonogram_dimensions};
+
+fuzz_target!(|input: (&str, &str)| {
+    let (col, row) = input;
+
+    // mirrors how the CLI and web endpoints bound each side by the other's rule count
+    let col = parse_nonogram_rules(col, row.split(';').count());
+    let row = parse_nonogram_rules(row, col.as_ref().map_or(0, Vec::len));
+
+    if let (Ok(col), Ok(row)) = (col, row) {
+        let _ = validate_nonogram_dimensions(&col, &row);
+    }
+});
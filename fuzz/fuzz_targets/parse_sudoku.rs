@@ -0,0 +1,9 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use puzzle_utils::{parse_sudoku, SudokuGrid};
+
+fuzz_target!(|puzzle: &str| {
+    let _ = parse_sudoku(puzzle);
+    let _ = SudokuGrid::parse(puzzle);
+});
@@ -0,0 +1,8 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use puzzle_utils::parse_sudoku_grid;
+
+fuzz_target!(|puzzle: &str| {
+    let _ = parse_sudoku_grid(puzzle);
+});
@@ -0,0 +1,109 @@
+use std::{
+    fs::{read_to_string, write},
+    path::Path,
+};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{MazeDirection, MazeNode, NonogramPuzzle, SudokuPuzzle};
+
+#[derive(Debug, Error)]
+pub enum PuzzleFileError {
+    #[error("could not access puzzle file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("invalid puzzle file: {0}")]
+    Format(#[from] serde_json::Error),
+}
+
+/// Any puzzle, stored as JSON tagged with its kind in a `type` field
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum Puzzle {
+    Maze {
+        width: usize,
+        height: usize,
+        grid: Vec<MazeNode>,
+        solution: Option<Vec<MazeDirection>>,
+    },
+    Sudoku(SudokuPuzzle),
+    Nonogram(NonogramPuzzle),
+}
+
+/// Writes the puzzle to 'path' as JSON, replacing the file if it exists
+pub fn save_puzzle(path: impl AsRef<Path>, puzzle: &Puzzle) -> Result<(), PuzzleFileError> {
+    write(path, serde_json::to_string_pretty(puzzle)?)?;
+
+    Ok(())
+}
+
+/// Reads a puzzle written by `save_puzzle`
+pub fn load_puzzle(path: impl AsRef<Path>) -> Result<Puzzle, PuzzleFileError> {
+    Ok(serde_json::from_str(&read_to_string(path)?)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env::temp_dir, fs::remove_file};
+
+    use crate::{
+        create_maze, parse_nonogram_rules, parse_sudoku, MazeAlgorithm, NonogramPuzzle,
+        SudokuPuzzle,
+    };
+
+    use super::{Puzzle, PuzzleFileError};
+
+    fn test_round_trip(name: &str, puzzle: Puzzle) {
+        let path = temp_dir().join(format!("puzzle-utils-{name}-{}.json", std::process::id()));
+
+        super::save_puzzle(&path, &puzzle).expect("should be ok");
+        let actual = super::load_puzzle(&path);
+        remove_file(&path).expect("should be ok");
+
+        assert_eq!(actual.expect("should be ok"), puzzle);
+    }
+
+    #[test]
+    fn maze_round_trip() {
+        let (grid, solution) =
+            create_maze(6, 4, MazeAlgorithm::RecursiveBacktrack).expect("should be ok");
+
+        test_round_trip(
+            "maze",
+            Puzzle::Maze {
+                width: 6,
+                height: 4,
+                grid,
+                solution: Some(solution),
+            },
+        );
+    }
+
+    #[test]
+    fn sudoku_round_trip() {
+        let givens = parse_sudoku(
+            "415830090003009104002150006900783000200000381500012400004900063380500040009307500",
+        )
+        .expect("should be ok");
+
+        test_round_trip("sudoku", Puzzle::Sudoku(SudokuPuzzle { givens }));
+    }
+
+    #[test]
+    fn nonogram_round_trip() {
+        test_round_trip(
+            "nonogram",
+            Puzzle::Nonogram(NonogramPuzzle {
+                col: parse_nonogram_rules("1,1;2", 3).expect("should be ok"),
+                row: parse_nonogram_rules("1;1;2", 2).expect("should be ok"),
+                solution: Some(vec![true, false, false, true, true, true]),
+            }),
+        );
+    }
+
+    #[test]
+    fn load_missing() {
+        let actual = super::load_puzzle(temp_dir().join("puzzle-utils-missing.json"));
+        assert!(matches!(actual, Err(PuzzleFileError::Io(_))));
+    }
+}
@@ -1,18 +1,50 @@
+pub mod io;
 pub mod puzzles;
 mod structures;
+#[cfg(test)]
+mod test_util;
 mod util;
+#[cfg(feature = "wasm")]
+pub mod wasm;
 
 use std::io::Cursor;
 
-use image::{ImageBuffer, ImageError, ImageFormat, Rgb};
+use image::{
+    codecs::jpeg::JpegEncoder, imageops::replace, ImageBuffer, ImageError, ImageFormat, Rgb,
+};
+
+use crate::util::WHITE_PIXEL;
 
 pub use crate::puzzles::maze::{
-    create_maze, print_maze, print_maze_solution, MazeAlgorithm, MazeDirection, MazeError, MazeNode,
+    animate_maze_solve, bitmask_to_maze, carve_rooms, create_maze, create_maze_instrumented,
+    create_maze_masked, create_maze_with_ends, create_maze_with_exit, create_maze_with_generator,
+    create_maze_with_loops, create_maze_with_stats, create_weave_maze, is_perfect_maze,
+    maze_dead_ends, maze_from_ascii, maze_neighbors, maze_shortest_path, maze_solution_to_svg,
+    maze_to_ascii, maze_to_bitmask, print_maze, print_maze_explored, print_maze_solution,
+    print_maze_solution_with_color, print_maze_solved, print_maze_thin, print_maze_with_options,
+    print_weave_maze, prune_short_dead_ends, solution_cells, solution_waypoints,
+    solve_maze_wall_follow, solve_to_any, Crossing, GenStats, Maze, MazeAlgorithm, MazeDirection,
+    MazeError, MazeEvent, MazeGenerator, MazeNode, MazeRenderOptions, WeaveCell, WeaveMaze,
 };
 pub use crate::puzzles::nonogram::{
-    parse_nonogram_rules, print_nonogram, print_nonogram_solution, solve_nonogram, NonogramError,
+    grade_nonogram, nonogram_difficulty, nonogram_sheet, nonogram_to_ascii, parse_nonogram,
+    parse_nonogram_rules, print_nonogram, print_nonogram_partial, print_nonogram_solution,
+    print_nonogram_solved, solve_nonogram, solve_nonogram_cancellable,
+    solve_nonogram_with_progress, validate_nonogram_collection, validate_nonogram_dimensions,
+    verify_nonogram, NonogramCell, NonogramError, NonogramPuzzle,
 };
-pub use crate::puzzles::sudoku::{parse_sudoku, print_sudoku, solve_sudoku, SudokuError};
+pub use crate::puzzles::sudoku::{
+    check_sudoku_solution, complete_sudoku, parse_sudoku, parse_sudoku_grid, print_sudoku,
+    print_sudoku_check, print_sudoku_highlighted, print_sudoku_solved, solve_sudoku,
+    solve_sudoku_cancellable, solve_sudoku_string, sudoku_candidates, sudoku_empty_cells,
+    sudoku_hint, validate_sudoku, SudokuError, SudokuGrid, SudokuPuzzle, Technique,
+};
+pub use crate::structures::dancing_links::{DancingLinksError, DancingMatrix, ExactCoverBuilder};
+pub use crate::structures::disjoint_set::DisjointSet;
+pub use crate::util::image_to_bool_grid;
+
+#[cfg(feature = "rayon")]
+pub use crate::puzzles::sudoku::solve_many;
 
 pub type RgbBuffer = ImageBuffer<Rgb<u8>, Vec<u8>>;
 
@@ -23,3 +55,113 @@ pub fn image_to_png_bytes(image: &RgbBuffer) -> Result<Vec<u8>, ImageError> {
 
     Ok(bytes)
 }
+
+/// The JPEG quality to use when the caller has no preference
+pub const DEFAULT_JPEG_QUALITY: u8 = 90;
+
+/// Converts a RgbBuffer to a Vec of bytes representing a JPEG, with 'quality' clamped to 1-100
+pub fn image_to_jpeg_bytes(image: &RgbBuffer, quality: u8) -> Result<Vec<u8>, ImageError> {
+    let mut bytes = Vec::new();
+    image.write_with_encoder(JpegEncoder::new_with_quality(
+        &mut bytes,
+        quality.clamp(1, 100),
+    ))?;
+
+    Ok(bytes)
+}
+
+/// A puzzle rendered both without and with its solution
+#[derive(Debug, Clone)]
+pub struct SolutionPair {
+    pub unsolved: RgbBuffer,
+    pub solved: RgbBuffer,
+}
+
+/// Encodes both images of a pair as PNGs, returning the unsolved bytes first
+pub fn solution_pair_to_png(pair: &SolutionPair) -> Result<(Vec<u8>, Vec<u8>), ImageError> {
+    Ok((
+        image_to_png_bytes(&pair.unsolved)?,
+        image_to_png_bytes(&pair.solved)?,
+    ))
+}
+
+/// Places the unsolved and solved images side by side, `gap` pixels apart, vertically centering the shorter one
+pub fn combine_solution_pair(pair: &SolutionPair, gap: u32) -> RgbBuffer {
+    let height = pair.unsolved.height().max(pair.solved.height());
+
+    let mut image = ImageBuffer::from_pixel(
+        pair.unsolved.width() + gap + pair.solved.width(),
+        height,
+        WHITE_PIXEL,
+    );
+
+    replace(
+        &mut image,
+        &pair.unsolved,
+        0,
+        ((height - pair.unsolved.height()) / 2) as i64,
+    );
+    replace(
+        &mut image,
+        &pair.solved,
+        (pair.unsolved.width() + gap) as i64,
+        ((height - pair.solved.height()) / 2) as i64,
+    );
+
+    image
+}
+
+#[cfg(test)]
+mod tests {
+    use image::ImageBuffer;
+
+    use crate::util::{BLACK_PIXEL, RED_PIXEL, WHITE_PIXEL};
+
+    use super::{image_to_png_bytes, SolutionPair};
+
+    #[test]
+    fn combine_solution_pair() {
+        let pair = SolutionPair {
+            unsolved: ImageBuffer::from_pixel(2, 4, BLACK_PIXEL),
+            solved: ImageBuffer::from_pixel(3, 2, RED_PIXEL),
+        };
+
+        let actual = super::combine_solution_pair(&pair, 1);
+        assert_eq!(actual.dimensions(), (6, 4));
+
+        assert_eq!(*actual.get_pixel(1, 0), BLACK_PIXEL);
+        assert_eq!(*actual.get_pixel(2, 1), WHITE_PIXEL);
+        assert_eq!(*actual.get_pixel(3, 0), WHITE_PIXEL);
+        assert_eq!(*actual.get_pixel(3, 1), RED_PIXEL);
+        assert_eq!(*actual.get_pixel(5, 2), RED_PIXEL);
+        assert_eq!(*actual.get_pixel(5, 3), WHITE_PIXEL);
+    }
+
+    #[test]
+    fn image_to_jpeg_bytes() {
+        let image = ImageBuffer::from_pixel(4, 4, RED_PIXEL);
+
+        for quality in [0, super::DEFAULT_JPEG_QUALITY, u8::MAX] {
+            let bytes = super::image_to_jpeg_bytes(&image, quality).expect("should be ok");
+            assert!(!bytes.is_empty());
+        }
+    }
+
+    #[test]
+    fn solution_pair_to_png() {
+        let pair = SolutionPair {
+            unsolved: ImageBuffer::from_pixel(2, 4, BLACK_PIXEL),
+            solved: ImageBuffer::from_pixel(3, 2, RED_PIXEL),
+        };
+
+        let (unsolved, solved) = super::solution_pair_to_png(&pair).expect("should be ok");
+        assert_eq!(
+            unsolved,
+            image_to_png_bytes(&pair.unsolved).expect("should be ok")
+        );
+        assert_eq!(
+            solved,
+            image_to_png_bytes(&pair.solved).expect("should be ok")
+        );
+    }
+}
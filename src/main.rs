@@ -0,0 +1,122 @@
+use std::{
+    error::Error,
+    fs::write,
+    io::{read_to_string, stdin, stdout, Write},
+    path::PathBuf,
+};
+
+use clap::{Parser, Subcommand};
+use puzzle_utils::{
+    create_maze, image_to_png_bytes, parse_nonogram, parse_sudoku, print_maze, print_maze_solved,
+    print_nonogram, print_nonogram_solved, print_sudoku, print_sudoku_solved, solve_nonogram,
+    MazeAlgorithm, NonogramPuzzle, RgbBuffer,
+};
+
+#[derive(Parser)]
+#[command(version, about)]
+struct Cli {
+    #[command(subcommand)]
+    puzzle: Puzzle,
+
+    /// Path to write the PNG to (defaults to stdout)
+    #[arg(long, global = true)]
+    out: Option<PathBuf>,
+}
+
+#[derive(Subcommand)]
+enum Puzzle {
+    /// Generate a maze
+    Maze {
+        #[arg(long, default_value_t = 10)]
+        width: usize,
+        #[arg(long, default_value_t = 10)]
+        height: usize,
+        /// Draw the solution path onto the maze
+        #[arg(long)]
+        solved: bool,
+    },
+    /// Solve a sudoku given as 81 digits (`0` for an empty space)
+    Sudoku {
+        /// The puzzle to solve (read from stdin if omitted)
+        #[arg(long)]
+        puzzle: Option<String>,
+        /// Render the puzzle without solving it
+        #[arg(long)]
+        unsolved: bool,
+    },
+    /// Solve a nonogram given as `;`-separated rules of `,`-separated runs
+    Nonogram {
+        #[arg(long)]
+        col: String,
+        #[arg(long)]
+        row: String,
+        /// Render the puzzle without solving it
+        #[arg(long)]
+        unsolved: bool,
+    },
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let cli = Cli::parse();
+
+    let image = match cli.puzzle {
+        Puzzle::Maze {
+            width,
+            height,
+            solved,
+        } => maze(width, height, solved)?,
+        Puzzle::Sudoku { puzzle, unsolved } => {
+            let puzzle = match puzzle {
+                Some(puzzle) => puzzle,
+                None => read_to_string(stdin())?,
+            };
+
+            sudoku(puzzle.trim(), unsolved)?
+        }
+        Puzzle::Nonogram { col, row, unsolved } => nonogram(&col, &row, unsolved)?,
+    };
+
+    let bytes = image_to_png_bytes(&image)?;
+    match cli.out {
+        Some(path) => write(path, bytes)?,
+        None => stdout().write_all(&bytes)?,
+    }
+
+    Ok(())
+}
+
+fn maze(width: usize, height: usize, solved: bool) -> Result<RgbBuffer, Box<dyn Error>> {
+    let (grid, solution) = create_maze(width, height, MazeAlgorithm::RecursiveBacktrack)?;
+
+    if !solved {
+        return Ok(print_maze(width, height, &grid)?);
+    }
+
+    Ok(print_maze_solved(width, height, &grid, &solution)?)
+}
+
+fn sudoku(puzzle: &str, unsolved: bool) -> Result<RgbBuffer, Box<dyn Error>> {
+    let puzzle = parse_sudoku(puzzle)?;
+
+    if unsolved {
+        return Ok(print_sudoku(&puzzle)?);
+    }
+
+    Ok(print_sudoku_solved(&puzzle)?)
+}
+
+fn nonogram(col: &str, row: &str, unsolved: bool) -> Result<RgbBuffer, Box<dyn Error>> {
+    let NonogramPuzzle { col, row, .. } = parse_nonogram(col, row)?;
+
+    if unsolved {
+        return Ok(print_nonogram(
+            col.len() as u32,
+            row.len() as u32,
+            &col,
+            &row,
+        )?);
+    }
+
+    let solution = solve_nonogram(&col, &row)?;
+    Ok(print_nonogram_solved(&col, &row, &solution)?)
+}
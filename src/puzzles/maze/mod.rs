@@ -1,31 +1,99 @@
 mod recursive_backtrack;
+mod weave;
 
-use std::collections::VecDeque;
+use std::{
+    cell::Cell,
+    cmp::{max, Reverse},
+    collections::{BinaryHeap, VecDeque},
+};
 
-use image::RgbImage;
+use image::{Rgb, RgbImage};
+use imageproc::{drawing::draw_filled_circle_mut, rect::Rect};
+use rand::{seq::SliceRandom, thread_rng, Rng, RngCore};
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 use crate::{
-    puzzles::maze::recursive_backtrack::recursive_backtrack,
-    util::{BLACK_PIXEL, RED_PIXEL, WHITE_PIXEL},
+    puzzles::maze::recursive_backtrack::{RecursiveBacktrack, RecursiveBacktrackBiased},
+    structures::{disjoint_set::DisjointSet, grid::Grid},
+    util::{BLACK_PIXEL, GREEN_PIXEL, LIGHT_GRAY_PIXEL, RED_PIXEL, WHITE_PIXEL},
     RgbBuffer,
 };
 
+pub use self::weave::{create_weave_maze, print_weave_maze, Crossing, WeaveCell, WeaveMaze};
+
 #[derive(Debug, Error)]
 pub enum MazeError {
     #[error("maze dimensions are invalid")]
     InvalidDimensions,
-    #[error("maze solution is invalid")]
-    InvalidSolution,
+    #[error("maze solution is invalid at step {0}")]
+    InvalidSolution(usize),
+    #[error("room does not fit inside the maze")]
+    InvalidRoom,
+    #[error("maze is not fully connected")]
+    Disconnected,
+    #[error("cell {0} is outside the maze")]
+    InvalidCell(usize),
+    #[error("maze is too large to render")]
+    TooLarge,
 }
 
 #[derive(Debug, Clone)]
 pub enum MazeAlgorithm {
     RecursiveBacktrack,
+    /// Recursive backtracking that favors horizontal moves by the given weight (0 to 1, 0.5 is unbiased)
+    RecursiveBacktrackBiased(f32),
+}
+
+impl MazeAlgorithm {
+    fn generator(&self) -> Box<dyn MazeGenerator> {
+        match *self {
+            MazeAlgorithm::RecursiveBacktrack => Box::new(RecursiveBacktrack),
+            MazeAlgorithm::RecursiveBacktrackBiased(horizontal_bias) => {
+                Box::new(RecursiveBacktrackBiased(horizontal_bias))
+            }
+        }
+    }
+
+    fn carve_masked(
+        &self,
+        width: usize,
+        height: usize,
+        mask: &[bool],
+        rng: &mut dyn RngCore,
+    ) -> Vec<MazeNode> {
+        match *self {
+            MazeAlgorithm::RecursiveBacktrack => {
+                RecursiveBacktrack.carve(width, height, mask, rng, &mut |_| {})
+            }
+            MazeAlgorithm::RecursiveBacktrackBiased(horizontal_bias) => RecursiveBacktrackBiased(
+                horizontal_bias,
+            )
+            .carve(width, height, mask, rng, &mut |_| {}),
+        }
+    }
+}
+
+/// An algorithm that carves passages between the cells of a fully walled grid
+///
+/// `create_maze_with_generator` rejects the result unless every cell is reachable
+pub trait MazeGenerator {
+    /// Returns `width * height` cells in row-major order
+    fn generate(&self, width: usize, height: usize, rng: &mut dyn RngCore) -> Vec<MazeNode>;
+
+    /// Same as `generate`, calling 'on_event' for every step taken, which by default reports nothing
+    fn generate_instrumented(
+        &self,
+        width: usize,
+        height: usize,
+        rng: &mut dyn RngCore,
+        _on_event: &mut dyn FnMut(MazeEvent),
+    ) -> Vec<MazeNode> {
+        self.generate(width, height, rng)
+    }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct MazeNode {
     right: bool,
     down: bool,
@@ -38,9 +106,14 @@ impl MazeNode {
             down: true,
         }
     }
+
+    /// Creates a cell with the given walls on its right and bottom sides
+    pub fn with_walls(right: bool, down: bool) -> Self {
+        MazeNode { right, down }
+    }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum MazeDirection {
     Right,
     Down,
@@ -48,6 +121,73 @@ pub enum MazeDirection {
     Up,
 }
 
+/// A step taken while generating a maze
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MazeEvent {
+    /// The generator moved onto a cell
+    Visit(usize),
+    /// The wall between two adjacent cells was removed
+    Carve { from: usize, to: usize },
+    /// The generator left a cell with no unvisited neighbors
+    Backtrack(usize),
+}
+
+/// How much work a generator did, tallied from its events
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GenStats {
+    /// Number of times the generator moved onto a cell
+    pub cells_visited: usize,
+    /// Number of times the generator backed out of a cell
+    pub backtracks: usize,
+    /// Deepest the generator's path got, in cells
+    pub max_stack_depth: usize,
+}
+
+/// A generated maze along with its solution and the cells the solution joins
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Maze {
+    pub width: usize,
+    pub height: usize,
+    pub grid: Vec<MazeNode>,
+    /// Ordered from the end back to the start, like the solution from `create_maze`
+    pub solution: Vec<MazeDirection>,
+    /// The top-row cell below the entrance
+    pub start: usize,
+    /// The bottom-row cell whose bottom wall is opened as the exit
+    pub end: usize,
+}
+
+/// Optional extras drawn by `print_maze_with_options`
+#[derive(Debug, Clone)]
+pub struct MazeRenderOptions {
+    /// Cell to mark with a green circle
+    pub start: Option<usize>,
+    /// Cell to mark with a red circle
+    pub end: Option<usize>,
+    /// Width in pixels of the outer wall, drawn inward and capped at 4 so cell centers stay clear
+    pub border_thickness: u32,
+}
+
+impl Default for MazeRenderOptions {
+    fn default() -> Self {
+        MazeRenderOptions {
+            start: None,
+            end: None,
+            border_thickness: 1,
+        }
+    }
+}
+
+const MAX_BORDER_THICKNESS: u32 = 4;
+
+/// The top-row cell whose north wall is left open as the maze's entrance
+const ENTRANCE: usize = 0;
+
+const NORTH_WALL: u8 = 0b0001;
+const EAST_WALL: u8 = 0b0010;
+const SOUTH_WALL: u8 = 0b0100;
+const WEST_WALL: u8 = 0b1000;
+
 #[derive(Clone)]
 enum PathNode {
     Start,
@@ -59,97 +199,351 @@ pub fn create_maze(
     width: usize,
     height: usize,
     algorithm: MazeAlgorithm,
-) -> (Vec<MazeNode>, Vec<MazeDirection>) {
-    let mut grid = match algorithm {
-        MazeAlgorithm::RecursiveBacktrack => recursive_backtrack(width, height),
-    };
+) -> Result<(Vec<MazeNode>, Vec<MazeDirection>), MazeError> {
+    create_maze_instrumented(width, height, algorithm, |_| {})
+}
 
-    let mut path_tree = vec![PathNode::Unvisited; width * height];
-    path_tree[0] = PathNode::Start;
+/// Same as `create_maze`, but returns a `Maze` that also records where the solution starts and ends
+pub fn create_maze_with_ends(
+    width: usize,
+    height: usize,
+    algorithm: MazeAlgorithm,
+) -> Result<Maze, MazeError> {
+    generate(width, height, &*algorithm.generator(), None, |_| {})
+}
 
-    let mut traversal = VecDeque::new();
-    traversal.push_back(0);
-
-    let mut found_paths = 0;
-
-    loop {
-        let coordinate = traversal
-            .pop_front()
-            .expect("the traversal should be non-empty");
-
-        if (coordinate / width) == (height - 1) {
-            found_paths += 1;
-
-            if found_paths == width {
-                grid[coordinate].down = false;
-                let mut current = coordinate;
-
-                let mut solution = Vec::new();
-                while let PathNode::Path(parent) = path_tree[current] {
-                    solution.push(if parent == current + 1 {
-                        MazeDirection::Left
-                    } else if parent == current + width {
-                        MazeDirection::Up
-                    } else if parent == current - 1 {
-                        MazeDirection::Right
-                    } else {
-                        MazeDirection::Down
-                    });
-
-                    current = parent;
-                }
+/// Same as `create_maze`, calling 'on_event' for every step the generator takes
+pub fn create_maze_instrumented(
+    width: usize,
+    height: usize,
+    algorithm: MazeAlgorithm,
+    on_event: impl FnMut(MazeEvent),
+) -> Result<(Vec<MazeNode>, Vec<MazeDirection>), MazeError> {
+    generate(width, height, &*algorithm.generator(), None, on_event)
+        .map(|maze| (maze.grid, maze.solution))
+}
+
+/// Same as `create_maze`, carving the maze with any generator
+pub fn create_maze_with_generator(
+    width: usize,
+    height: usize,
+    generator: &dyn MazeGenerator,
+) -> Result<(Vec<MazeNode>, Vec<MazeDirection>), MazeError> {
+    generate(width, height, generator, None, |_| {}).map(|maze| (maze.grid, maze.solution))
+}
+
+/// Same as `create_maze`, also returning how much work the generator did
+pub fn create_maze_with_stats(
+    width: usize,
+    height: usize,
+    algorithm: MazeAlgorithm,
+) -> Result<(Vec<MazeNode>, Vec<MazeDirection>, GenStats), MazeError> {
+    let mut stats = GenStats::default();
+    let mut depth = 0;
 
-                return (grid, solution);
+    let (grid, solution) =
+        create_maze_instrumented(width, height, algorithm, |event| match event {
+            MazeEvent::Visit(_) => {
+                stats.cells_visited += 1;
+                depth += 1;
+                stats.max_stack_depth = max(stats.max_stack_depth, depth);
             }
-        }
+            MazeEvent::Backtrack(_) => {
+                stats.backtracks += 1;
+                depth -= 1;
+            }
+            MazeEvent::Carve { .. } => {}
+        })?;
 
-        let right = coordinate + 1;
-        if !grid[coordinate].right && matches!(path_tree[right], PathNode::Unvisited) {
-            path_tree[right] = PathNode::Path(coordinate);
-            traversal.push_back(right);
-        }
+    Ok((grid, solution, stats))
+}
+
+/// Same as `create_maze`, but once the maze is carved, up to 'extra_connections' more random walls are removed,
+/// each adding one loop
+///
+/// Also returns how many walls were removed, which is fewer than asked for only if the maze runs out of walls
+pub fn create_maze_with_loops(
+    width: usize,
+    height: usize,
+    algorithm: MazeAlgorithm,
+    extra_connections: usize,
+) -> Result<(Vec<MazeNode>, Vec<MazeDirection>, usize), MazeError> {
+    let generator = WithLoops {
+        generator: algorithm.generator(),
+        extra_connections,
+        added: Cell::new(0),
+    };
+
+    let maze = generate(width, height, &generator, None, |_| {})?;
 
-        let down = coordinate + width;
-        if !grid[coordinate].down && matches!(path_tree[down], PathNode::Unvisited) {
-            path_tree[down] = PathNode::Path(coordinate);
-            traversal.push_back(down);
+    Ok((maze.grid, maze.solution, generator.added.get()))
+}
+
+/// Wraps a generator, knocking down extra walls after it finishes
+struct WithLoops {
+    generator: Box<dyn MazeGenerator>,
+    extra_connections: usize,
+    added: Cell<usize>,
+}
+
+impl MazeGenerator for WithLoops {
+    fn generate(&self, width: usize, height: usize, rng: &mut dyn RngCore) -> Vec<MazeNode> {
+        self.generate_instrumented(width, height, rng, &mut |_| {})
+    }
+
+    fn generate_instrumented(
+        &self,
+        width: usize,
+        height: usize,
+        rng: &mut dyn RngCore,
+        on_event: &mut dyn FnMut(MazeEvent),
+    ) -> Vec<MazeNode> {
+        let mut grid = self
+            .generator
+            .generate_instrumented(width, height, rng, on_event);
+
+        if !matches_dimensions(width, height, grid.len()) {
+            return grid;
         }
 
-        if let Some(left) = coordinate.checked_sub(1) {
-            if !grid[left].right && matches!(path_tree[left], PathNode::Unvisited) {
-                path_tree[left] = PathNode::Path(coordinate);
-                traversal.push_back(left);
+        let mut walls = Vec::new();
+        for (coordinate, node) in grid.iter().enumerate() {
+            if node.right && (coordinate + 1) % width != 0 {
+                walls.push((coordinate, coordinate + 1));
+            }
+
+            if node.down && coordinate + width < grid.len() {
+                walls.push((coordinate, coordinate + width));
             }
         }
 
-        if let Some(up) = coordinate.checked_sub(width) {
-            if !grid[up].down && matches!(path_tree[up], PathNode::Unvisited) {
-                path_tree[up] = PathNode::Path(coordinate);
-                traversal.push_back(up);
+        let (removed, _) = walls.partial_shuffle(rng, self.extra_connections);
+
+        for &(from, to) in removed.iter() {
+            if to == from + 1 {
+                grid[from].right = false;
+            } else {
+                grid[from].down = false;
             }
+
+            on_event(MazeEvent::Carve { from, to });
         }
+
+        self.added.set(removed.len());
+
+        grid
+    }
+}
+
+/// Same as `create_maze`, but the exit is opened below 'exit', which must be in the bottom row
+pub fn create_maze_with_exit(
+    width: usize,
+    height: usize,
+    algorithm: MazeAlgorithm,
+    exit: usize,
+) -> Result<(Vec<MazeNode>, Vec<MazeDirection>), MazeError> {
+    if width == 0 || height == 0 || width.checked_mul(height).is_none() {
+        return Err(MazeError::InvalidDimensions);
+    }
+
+    if exit / width != height - 1 || exit >= width * height {
+        return Err(MazeError::InvalidCell(exit));
+    }
+
+    generate(width, height, &*algorithm.generator(), Some(exit), |_| {})
+        .map(|maze| (maze.grid, maze.solution))
+}
+
+/// Same as `create_maze`, but only the cells marked `true` in 'mask' are carved, and the rest are left walled off
+///
+/// The entrance must be masked, since the solution starts there. It ends at the cell of the lowest masked row reached
+/// last by a breadth-first search, whose bottom wall is opened if it's on the maze's edge
+pub fn create_maze_masked(
+    width: usize,
+    height: usize,
+    mask: &[bool],
+    algorithm: MazeAlgorithm,
+) -> Result<(Vec<MazeNode>, Vec<MazeDirection>), MazeError> {
+    if width == 0 || height == 0 || !matches_dimensions(width, height, mask.len()) {
+        return Err(MazeError::InvalidDimensions);
+    }
+
+    if !mask.contains(&true) {
+        return Err(MazeError::InvalidDimensions);
+    }
+
+    if !mask[ENTRANCE] {
+        return Err(MazeError::InvalidCell(ENTRANCE));
+    }
+
+    let mut grid = algorithm.carve_masked(width, height, mask, &mut thread_rng());
+
+    let (order, path_tree) = traverse(width, height, &grid, ENTRANCE);
+
+    if order.len() != mask.iter().filter(|&&cell| cell).count() {
+        return Err(MazeError::Disconnected);
+    }
+
+    let bottom = order
+        .iter()
+        .map(|coordinate| coordinate / width)
+        .max()
+        .expect("order should contain the start");
+    let exit = *order
+        .iter()
+        .rev()
+        .find(|&&coordinate| coordinate / width == bottom)
+        .expect("the bottom row should be reachable");
+
+    if bottom == height - 1 {
+        grid[exit].down = false;
     }
+
+    Ok((grid, path_to(width, &path_tree, exit)))
 }
 
-pub fn print_maze(width: u32, height: u32, grid: &[MazeNode]) -> Result<RgbBuffer, MazeError> {
-    if width as usize * height as usize != grid.len() {
+/// Carves a maze and opens its exit, defaulting to the bottom-row cell reached last by a breadth-first search
+fn generate(
+    width: usize,
+    height: usize,
+    generator: &dyn MazeGenerator,
+    exit: Option<usize>,
+    mut on_event: impl FnMut(MazeEvent),
+) -> Result<Maze, MazeError> {
+    if width == 0 || height == 0 || width.checked_mul(height).is_none() {
+        return Err(MazeError::InvalidDimensions);
+    }
+
+    let mut grid = generator.generate_instrumented(width, height, &mut thread_rng(), &mut on_event);
+
+    if !matches_dimensions(width, height, grid.len()) {
         return Err(MazeError::InvalidDimensions);
     }
 
-    let mut image = RgbImage::from_pixel(width * 10 + 1, height * 10 + 1, WHITE_PIXEL);
+    if !is_connected(width, &grid) {
+        return Err(MazeError::Disconnected);
+    }
+
+    let (order, path_tree) = traverse(width, height, &grid, ENTRANCE);
+
+    let exit = exit.unwrap_or_else(|| {
+        *order
+            .iter()
+            .rev()
+            .find(|&&coordinate| coordinate / width == height - 1)
+            .expect("the bottom row should be reachable")
+    });
+
+    grid[exit].down = false;
+
+    Ok(Maze {
+        width,
+        height,
+        solution: path_to(width, &path_tree, exit),
+        grid,
+        start: ENTRANCE,
+        end: exit,
+    })
+}
+
+/// Finds the goal nearest to `start` and the path to it, ordered from the goal back to the start
+pub fn solve_to_any(
+    width: usize,
+    height: usize,
+    grid: &[MazeNode],
+    start: usize,
+    goals: &[usize],
+) -> Option<(usize, Vec<MazeDirection>)> {
+    if !matches_dimensions(width, height, grid.len()) || start >= grid.len() {
+        return None;
+    }
+
+    let (order, path_tree) = traverse(width, height, grid, start);
+
+    let goal = *order.iter().find(|coordinate| goals.contains(coordinate))?;
+
+    Some((goal, path_to(width, &path_tree, goal)))
+}
+
+/// Breadth-first search from `start`, returning the cells in the order they were reached and each cell's parent
+fn traverse(
+    width: usize,
+    height: usize,
+    grid: &[MazeNode],
+    start: usize,
+) -> (Vec<usize>, Vec<PathNode>) {
+    let mut path_tree = vec![PathNode::Unvisited; width * height];
+    path_tree[start] = PathNode::Start;
+
+    let mut order = Vec::with_capacity(width * height);
+
+    let mut traversal = VecDeque::new();
+    traversal.push_back(start);
+
+    while let Some(coordinate) = traversal.pop_front() {
+        order.push(coordinate);
+
+        for neighbor in open_neighbors(width, height, grid, coordinate) {
+            if matches!(path_tree[neighbor], PathNode::Unvisited) {
+                path_tree[neighbor] = PathNode::Path(coordinate);
+                traversal.push_back(neighbor);
+            }
+        }
+    }
+
+    (order, path_tree)
+}
+
+fn path_to(width: usize, path_tree: &[PathNode], end: usize) -> Vec<MazeDirection> {
+    let mut current = end;
+
+    let mut solution = Vec::new();
+    while let PathNode::Path(parent) = path_tree[current] {
+        solution.push(if parent == current + 1 {
+            MazeDirection::Left
+        } else if parent == current + width {
+            MazeDirection::Up
+        } else if parent + 1 == current {
+            MazeDirection::Right
+        } else {
+            MazeDirection::Down
+        });
+
+        current = parent;
+    }
+
+    solution
+}
+
+pub fn print_maze(width: usize, height: usize, grid: &[MazeNode]) -> Result<RgbBuffer, MazeError> {
+    print_maze_with_options(width, height, grid, &MazeRenderOptions::default())
+}
 
-    for row in 0..image.height() {
-        image.put_pixel(0, row, BLACK_PIXEL);
+pub fn print_maze_with_options(
+    width: usize,
+    height: usize,
+    grid: &[MazeNode],
+    options: &MazeRenderOptions,
+) -> Result<RgbBuffer, MazeError> {
+    if !matches_dimensions(width, height, grid.len()) {
+        return Err(MazeError::InvalidDimensions);
     }
 
-    for col in 10..image.width() {
-        image.put_pixel(col, 0, BLACK_PIXEL);
+    for cell in [options.start, options.end].into_iter().flatten() {
+        if cell >= grid.len() {
+            return Err(MazeError::InvalidCell(cell));
+        }
     }
 
+    let mut image = RgbImage::from_pixel(
+        pixel_length(width, 10)? + 1,
+        pixel_length(height, 10)? + 1,
+        WHITE_PIXEL,
+    );
+
     for (i, node) in grid.iter().enumerate() {
-        let idx = i as u32;
-        let x = idx % width;
-        let y = idx / width;
+        let x = (i % width) as u32;
+        let y = (i / width) as u32;
 
         if node.right {
             for k in 0..=10 {
@@ -164,80 +558,1832 @@ pub fn print_maze(width: u32, height: u32, grid: &[MazeNode]) -> Result<RgbBuffe
         }
     }
 
+    draw_border(&mut image, width, grid, options.border_thickness);
+
+    for (cell, color) in [(options.start, GREEN_PIXEL), (options.end, RED_PIXEL)] {
+        if let Some(cell) = cell {
+            let x = (cell % width) as u32 * 10 + 5;
+            let y = (cell / width) as u32 * 10 + 5;
+
+            draw_filled_circle_mut(&mut image, (x as i32, y as i32), 3, color);
+        }
+    }
+
     Ok(image)
 }
 
-pub fn print_maze_solution(
-    mut unsolved: RgbBuffer,
-    solution: &[MazeDirection],
+/// Renders the maze at one pixel per cell and per wall, giving a 2 * width + 1 by 2 * height + 1 image
+///
+/// Cell (x, y) is the pixel (2x + 1, 2y + 1), the pixels between neighboring cells are the walls between them, and
+/// pixels with both coordinates even are always wall
+pub fn print_maze_thin(
+    width: usize,
+    height: usize,
+    grid: &[MazeNode],
 ) -> Result<RgbBuffer, MazeError> {
-    if unsolved.width() < 6 || unsolved.height() < 6 {
+    if !matches_dimensions(width, height, grid.len()) {
         return Err(MazeError::InvalidDimensions);
     }
 
-    let mut x = 0;
-    let mut y = 0;
+    let mut image = RgbImage::from_pixel(
+        pixel_length(width, 2)? + 1,
+        pixel_length(height, 2)? + 1,
+        BLACK_PIXEL,
+    );
 
-    for k in 0..=5 {
-        unsolved.put_pixel(x + 5, y + k, RED_PIXEL);
+    if !grid.is_empty() {
+        image.put_pixel(ENTRANCE as u32 * 2 + 1, 0, WHITE_PIXEL);
     }
 
-    for step in solution.iter().rev() {
-        match step {
-            MazeDirection::Right => {
-                if unsolved.width() < x * 10 + 16 || unsolved.height() < y * 10 + 6 {
-                    return Err(MazeError::InvalidSolution);
-                }
+    for (i, node) in grid.iter().enumerate() {
+        let x = (i % width) as u32 * 2 + 1;
+        let y = (i / width) as u32 * 2 + 1;
 
-                for k in 0..=10 {
-                    unsolved.put_pixel(x * 10 + k + 5, y * 10 + 5, RED_PIXEL);
-                }
+        image.put_pixel(x, y, WHITE_PIXEL);
 
-                x += 1;
-            }
-            MazeDirection::Down => {
-                if unsolved.width() < x * 10 + 6 || unsolved.height() < y * 10 + 16 {
-                    return Err(MazeError::InvalidSolution);
-                }
+        if !node.right {
+            image.put_pixel(x + 1, y, WHITE_PIXEL);
+        }
 
-                for k in 0..=10 {
-                    unsolved.put_pixel(x * 10 + 5, y * 10 + k + 5, RED_PIXEL);
-                }
+        if !node.down {
+            image.put_pixel(x, y + 1, WHITE_PIXEL);
+        }
+    }
 
-                y += 1;
+    Ok(image)
+}
+
+/// Draws the outer wall `thickness` pixels deep, leaving the entrance and any openings in the last row or column
+fn draw_border(image: &mut RgbImage, width: usize, grid: &[MazeNode], thickness: u32) {
+    // An empty maze has no walls to draw, and its image may be too thin for even a one pixel border
+    if grid.is_empty() {
+        return;
+    }
+
+    let thickness = thickness.clamp(1, MAX_BORDER_THICKNESS);
+    let (image_width, image_height) = image.dimensions();
+    let height = grid.len() / width;
+
+    // Pixels strictly inside a cell's span along a wall, between the corner posts
+    let opening = |cell: usize, k: u32| (cell as u32 * 10 + 1..=cell as u32 * 10 + 9).contains(&k);
+
+    for col in 0..image_width {
+        let cell = (col / 10) as usize;
+
+        for depth in 0..thickness {
+            if !opening(ENTRANCE, col) {
+                image.put_pixel(col, depth, BLACK_PIXEL);
             }
-            MazeDirection::Left => {
-                if x * 10 < 15 || unsolved.height() < y * 10 + 6 {
-                    return Err(MazeError::InvalidSolution);
-                }
 
-                for k in 0..=10 {
-                    unsolved.put_pixel(x * 10 - k + 5, y * 10 + 5, RED_PIXEL);
-                }
+            let open = cell < width && !grid[(height - 1) * width + cell].down;
+            if !(open && opening(cell, col)) {
+                image.put_pixel(col, image_height - 1 - depth, BLACK_PIXEL);
+            }
+        }
+    }
 
-                x -= 1;
+    for row in 0..image_height {
+        let cell = (row / 10) as usize;
+
+        for depth in 0..thickness {
+            image.put_pixel(depth, row, BLACK_PIXEL);
+
+            let open = cell < height && !grid[cell * width + width - 1].right;
+            if !(open && opening(cell, row)) {
+                image.put_pixel(image_width - 1 - depth, row, BLACK_PIXEL);
             }
-            MazeDirection::Up => {
-                if unsolved.width() < x * 10 + 6 || y * 10 < 15 {
-                    return Err(MazeError::InvalidSolution);
-                }
+        }
+    }
+}
 
-                for k in 0..=10 {
-                    unsolved.put_pixel(x * 10 + 5, y * 10 - k + 5, RED_PIXEL);
-                }
+/// Renders breadth-first search from `start` to `end` as frames the size of `print_maze`, one per layer of the search
+///
+/// Each frame tints the cells already searched light gray and the frontier green, and a final frame adds the path
+/// found in red. Fails with `Disconnected` if `end` can't be reached
+pub fn animate_maze_solve(
+    width: usize,
+    height: usize,
+    grid: &[MazeNode],
+    start: usize,
+    end: usize,
+) -> Result<Vec<RgbBuffer>, MazeError> {
+    let unsolved = print_maze(width, height, grid)?;
 
-                y -= 1;
+    for cell in [start, end] {
+        if cell >= grid.len() {
+            return Err(MazeError::InvalidCell(cell));
+        }
+    }
+
+    let (order, path_tree) = traverse(width, height, grid, start);
+
+    // Parents are always reached before their children, so one pass over the order fills in every depth
+    let mut depths = vec![None; grid.len()];
+    for &cell in &order {
+        depths[cell] = Some(match path_tree[cell] {
+            PathNode::Path(parent) => depths[parent].map_or(0, |depth| depth + 1),
+            _ => 0,
+        });
+    }
+
+    let end_depth = depths[end].ok_or(MazeError::Disconnected)?;
+
+    let mut frames = Vec::with_capacity(end_depth + 2);
+    for layer in 0..=end_depth {
+        let mut frame = unsolved.clone();
+
+        for &cell in &order {
+            match depths[cell] {
+                Some(depth) if depth < layer => {
+                    tint_cell(&mut frame, width, cell, LIGHT_GRAY_PIXEL)
+                }
+                Some(depth) if depth == layer => tint_cell(&mut frame, width, cell, GREEN_PIXEL),
+                _ => {}
             }
         }
+
+        frames.push(frame);
+    }
+
+    let mut solved = frames[end_depth].clone();
+    let cells = solution_cells(width, start, &path_to(width, &path_tree, end));
+    draw_cell_path(&mut solved, width, &cells, RED_PIXEL);
+    frames.push(solved);
+
+    Ok(frames)
+}
+
+/// Renders the maze with every cell marked in 'visited' tinted 'color', to show how much of it a solver searched
+pub fn print_maze_explored(
+    width: usize,
+    height: usize,
+    grid: &[MazeNode],
+    visited: &[bool],
+    color: Rgb<u8>,
+) -> Result<RgbBuffer, MazeError> {
+    if visited.len() != grid.len() {
+        return Err(MazeError::InvalidDimensions);
+    }
+
+    let mut image = print_maze(width, height, grid)?;
+
+    for (cell, _) in visited.iter().enumerate().filter(|&(_, &visited)| visited) {
+        tint_cell(&mut image, width, cell, color);
+    }
+
+    Ok(image)
+}
+
+/// Fills the inside of a cell drawn by `print_maze`, leaving its walls and corners untouched
+fn tint_cell(image: &mut RgbImage, width: usize, cell: usize, color: Rgb<u8>) {
+    let x = (cell % width) as u32 * 10;
+    let y = (cell / width) as u32 * 10;
+
+    for dy in 1..=9 {
+        for dx in 1..=9 {
+            image.put_pixel(x + dx, y + dy, color);
+        }
     }
+}
+
+/// Draws a line through the centers of a chain of adjacent cells drawn by `print_maze`
+fn draw_cell_path(image: &mut RgbImage, width: usize, cells: &[usize], color: Rgb<u8>) {
+    let center = |cell: usize| {
+        (
+            (cell % width) as u32 * 10 + 5,
+            (cell / width) as u32 * 10 + 5,
+        )
+    };
 
-    if unsolved.width() < x * 10 + 6 || unsolved.height() < y * 10 + 16 {
-        return Err(MazeError::InvalidSolution);
+    for &cell in cells {
+        let (x, y) = center(cell);
+        image.put_pixel(x, y, color);
     }
 
-    for k in 1..=5 {
-        unsolved.put_pixel(x * 10 + 5, y * 10 + k + 5, RED_PIXEL);
+    for pair in cells.windows(2) {
+        let (x, y) = center(pair[0].min(pair[1]));
+        let horizontal = pair[0].abs_diff(pair[1]) == 1;
+
+        for k in 0..=10 {
+            if horizontal {
+                image.put_pixel(x + k, y, color);
+            } else {
+                image.put_pixel(x, y + k, color);
+            }
+        }
     }
+}
 
-    Ok(unsolved)
+/// Renders the maze with its solution drawn on top
+pub fn print_maze_solved(
+    width: usize,
+    height: usize,
+    grid: &[MazeNode],
+    solution: &[MazeDirection],
+) -> Result<RgbBuffer, MazeError> {
+    print_maze_solution(print_maze(width, height, grid)?, grid, solution)
+}
+
+/// Checks that a `width` by `height` grid has exactly `len` cells, without overflowing on huge dimensions
+pub(crate) fn matches_dimensions(width: usize, height: usize, len: usize) -> bool {
+    width.checked_mul(height) == Some(len)
+}
+
+/// Converts a length in cells to pixels, failing if it doesn't leave room for a border pixel within a u32
+pub(crate) fn pixel_length(cells: usize, cell_size: u32) -> Result<u32, MazeError> {
+    u32::try_from(cells)
+        .ok()
+        .and_then(|cells| cells.checked_mul(cell_size))
+        .filter(|&pixels| pixels < u32::MAX)
+        .ok_or(MazeError::TooLarge)
+}
+
+pub fn print_maze_solution(
+    unsolved: RgbBuffer,
+    grid: &[MazeNode],
+    solution: &[MazeDirection],
+) -> Result<RgbBuffer, MazeError> {
+    print_maze_solution_with_color(unsolved, grid, solution, RED_PIXEL)
+}
+
+/// Same as `print_maze_solution`, drawing the path in 'color' instead of red
+pub fn print_maze_solution_with_color(
+    mut unsolved: RgbBuffer,
+    grid: &[MazeNode],
+    solution: &[MazeDirection],
+    color: Rgb<u8>,
+) -> Result<RgbBuffer, MazeError> {
+    let width = unsolved.width().saturating_sub(1) / 10;
+    let height = unsolved.height().saturating_sub(1) / 10;
+
+    if width == 0
+        || height == 0
+        || unsolved.width() != width * 10 + 1
+        || unsolved.height() != height * 10 + 1
+    {
+        return Err(MazeError::InvalidDimensions);
+    }
+
+    validate_solution(width as usize, height as usize, grid, ENTRANCE, solution)?;
+
+    let mut x = ENTRANCE as u32;
+    let mut y = 0;
+
+    for k in 0..=5 {
+        unsolved.put_pixel(x * 10 + 5, y + k, color);
+    }
+
+    for step in solution.iter().rev() {
+        match step {
+            MazeDirection::Right => {
+                for k in 0..=10 {
+                    unsolved.put_pixel(x * 10 + k + 5, y * 10 + 5, color);
+                }
+
+                x += 1;
+            }
+            MazeDirection::Down => {
+                for k in 0..=10 {
+                    unsolved.put_pixel(x * 10 + 5, y * 10 + k + 5, color);
+                }
+
+                y += 1;
+            }
+            MazeDirection::Left => {
+                for k in 0..=10 {
+                    unsolved.put_pixel(x * 10 - k + 5, y * 10 + 5, color);
+                }
+
+                x -= 1;
+            }
+            MazeDirection::Up => {
+                for k in 0..=10 {
+                    unsolved.put_pixel(x * 10 + 5, y * 10 - k + 5, color);
+                }
+
+                y -= 1;
+            }
+        }
+    }
+
+    for k in 1..=5 {
+        unsolved.put_pixel(x * 10 + 5, y * 10 + k + 5, color);
+    }
+
+    Ok(unsolved)
+}
+
+/// Renders the solution as a lone SVG `<polyline>` through the centers of `cell_size` pixel cells, for overlaying
+/// on a drawing of the maze with its top left corner at the origin
+///
+/// Like `print_maze_solution`, the line runs out through the entrance and exit when the path starts or ends at them
+pub fn maze_solution_to_svg(
+    width: usize,
+    height: usize,
+    grid: &[MazeNode],
+    start: usize,
+    solution: &[MazeDirection],
+    cell_size: u32,
+) -> Result<String, MazeError> {
+    validate_solution(width, height, grid, start, solution)?;
+
+    let cell_size = cell_size as f32;
+    let center = |cell: usize| {
+        (
+            (cell % width) as f32 * cell_size + cell_size / 2.0,
+            (cell / width) as f32 * cell_size + cell_size / 2.0,
+        )
+    };
+
+    let cells = solution_cells(width, start, solution);
+    let mut points = cells.iter().map(|&cell| center(cell)).collect::<Vec<_>>();
+
+    if start == ENTRANCE {
+        points.insert(0, (center(start).0, 0.0));
+    }
+
+    let end = *cells.last().expect("cells should start with `start`");
+    if end / width == height - 1 && !grid[end].down {
+        points.push((center(end).0, height as f32 * cell_size));
+    }
+
+    let points = points
+        .iter()
+        .map(|(x, y)| format!("{x},{y}"))
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    Ok(format!(
+        r#"<polyline points="{points}" fill="none" stroke="red" stroke-width="{}"/>"#,
+        cell_size / 4.0
+    ))
+}
+
+/// Checks that following the solution from `start` never walks through a wall or off the grid
+///
+/// The solution is read in the same order as `create_maze` produces it, from the last step back to the first, and
+/// an error holds the index into `solution` of the offending step
+pub fn validate_solution(
+    width: usize,
+    height: usize,
+    grid: &[MazeNode],
+    start: usize,
+    solution: &[MazeDirection],
+) -> Result<(), MazeError> {
+    if !matches_dimensions(width, height, grid.len()) {
+        return Err(MazeError::InvalidDimensions);
+    }
+
+    if start >= grid.len() {
+        return Err(MazeError::InvalidCell(start));
+    }
+
+    let mut current = start;
+
+    for (index, step) in solution.iter().enumerate().rev() {
+        let next = match step {
+            MazeDirection::Right => current + 1,
+            MazeDirection::Down => current + width,
+            MazeDirection::Left => current.wrapping_sub(1),
+            MazeDirection::Up => current.wrapping_sub(width),
+        };
+
+        if !open_neighbors(width, height, grid, current).contains(&next) {
+            return Err(MazeError::InvalidSolution(index));
+        }
+
+        current = next;
+    }
+
+    Ok(())
+}
+
+/// Lists the cells visited by following the solution from `start`, beginning with `start` itself
+///
+/// The solution is read from its last step back to its first, like `validate_solution`, which should be used to
+/// check it beforehand
+pub fn solution_cells(width: usize, start: usize, solution: &[MazeDirection]) -> Vec<usize> {
+    let mut cells = Vec::with_capacity(solution.len() + 1);
+    cells.push(start);
+
+    let mut current = start;
+    for step in solution.iter().rev() {
+        current = match step {
+            MazeDirection::Right => current + 1,
+            MazeDirection::Down => current + width,
+            MazeDirection::Left => current.wrapping_sub(1),
+            MazeDirection::Up => current.wrapping_sub(width),
+        };
+        cells.push(current);
+    }
+
+    cells
+}
+
+/// Same as `solution_cells`, but gives each cell as (x, y) coordinates
+pub fn solution_waypoints(
+    width: usize,
+    start: usize,
+    solution: &[MazeDirection],
+) -> Vec<(usize, usize)> {
+    solution_cells(width, start, solution)
+        .into_iter()
+        .map(|cell| (cell % width, cell / width))
+        .collect()
+}
+
+/// Converts a maze to one byte per cell, where the low nibble has a bit set for each wall around the cell:
+/// `0b0001` north, `0b0010` east, `0b0100` south, and `0b1000` west
+///
+/// The outer north and west boundaries are always walls, except for the entrance above the first cell
+pub fn maze_to_bitmask(
+    width: usize,
+    height: usize,
+    grid: &[MazeNode],
+) -> Result<Vec<u8>, MazeError> {
+    if !matches_dimensions(width, height, grid.len()) {
+        return Err(MazeError::InvalidDimensions);
+    }
+
+    Ok(grid
+        .iter()
+        .enumerate()
+        .map(|(i, node)| {
+            let mut walls = 0;
+
+            let north = match i.checked_sub(width) {
+                Some(up) => grid[up].down,
+                None => i != ENTRANCE,
+            };
+
+            if north {
+                walls |= NORTH_WALL;
+            }
+
+            if node.right {
+                walls |= EAST_WALL;
+            }
+
+            if node.down {
+                walls |= SOUTH_WALL;
+            }
+
+            if i % width == 0 || grid[i - 1].right {
+                walls |= WEST_WALL;
+            }
+
+            walls
+        })
+        .collect())
+}
+
+/// Converts a bitmask produced by `maze_to_bitmask` back into a maze
+///
+/// Only the east and south bits are read, since the north and west walls are owned by neighboring cells
+pub fn bitmask_to_maze(
+    width: usize,
+    height: usize,
+    bitmask: &[u8],
+) -> Result<Vec<MazeNode>, MazeError> {
+    if !matches_dimensions(width, height, bitmask.len()) {
+        return Err(MazeError::InvalidDimensions);
+    }
+
+    Ok(bitmask
+        .iter()
+        .map(|walls| MazeNode {
+            right: walls & EAST_WALL != 0,
+            down: walls & SOUTH_WALL != 0,
+        })
+        .collect())
+}
+
+/// Draws a maze as text, using `+` for corners, `--` for horizontal walls, and `|` for vertical walls
+pub fn maze_to_ascii(width: usize, height: usize, grid: &[MazeNode]) -> Result<String, MazeError> {
+    if width == 0 || !matches_dimensions(width, height, grid.len()) {
+        return Err(MazeError::InvalidDimensions);
+    }
+
+    let mut ascii = String::from("+");
+    for x in 0..width {
+        ascii.push_str(if x == ENTRANCE { "  +" } else { "--+" });
+    }
+    ascii.push('\n');
+
+    for row in grid.chunks(width) {
+        ascii.push('|');
+        for node in row {
+            ascii.push_str(if node.right { "  |" } else { "   " });
+        }
+        ascii.push_str("\n+");
+
+        for node in row {
+            ascii.push_str(if node.down { "--+" } else { "  +" });
+        }
+        ascii.push('\n');
+    }
+
+    Ok(ascii)
+}
+
+/// Parses a maze drawn in the format produced by `maze_to_ascii`, returning its width, height, and grid
+pub fn maze_from_ascii(text: &str) -> Result<(usize, usize, Vec<MazeNode>), MazeError> {
+    let lines = text.lines().map(str::as_bytes).collect::<Vec<_>>();
+
+    let line_width = lines.first().map_or(0, |line| line.len());
+    if lines.len() < 3
+        || lines.len() % 2 == 0
+        || line_width < 4
+        || (line_width - 1) % 3 != 0
+        || lines.iter().any(|line| line.len() != line_width)
+    {
+        return Err(MazeError::InvalidDimensions);
+    }
+
+    let width = (line_width - 1) / 3;
+    let height = (lines.len() - 1) / 2;
+
+    let mut grid = Vec::with_capacity(width * height);
+    for y in 0..height {
+        for x in 0..width {
+            grid.push(MazeNode {
+                right: lines[y * 2 + 1][x * 3 + 3] == b'|',
+                down: lines[y * 2 + 2][x * 3 + 1] == b'-',
+            });
+        }
+    }
+
+    Ok((width, height, grid))
+}
+
+/// Returns every cell with exactly one open passage to a neighboring cell
+pub fn maze_dead_ends(
+    width: usize,
+    height: usize,
+    grid: &[MazeNode],
+) -> Result<Vec<usize>, MazeError> {
+    if !matches_dimensions(width, height, grid.len()) {
+        return Err(MazeError::InvalidDimensions);
+    }
+
+    Ok((0..grid.len())
+        .filter(|&coordinate| open_neighbors(width, height, grid, coordinate).len() == 1)
+        .collect())
+}
+
+/// Checks that every cell is reachable by exactly one path, meaning the maze is connected and has no loops
+pub fn is_perfect_maze(width: usize, height: usize, grid: &[MazeNode]) -> bool {
+    if !matches_dimensions(width, height, grid.len()) || grid.is_empty() {
+        return false;
+    }
+
+    let mut connections = DisjointSet::with_size(grid.len());
+    let mut passages = 0;
+
+    for coordinate in 0..grid.len() {
+        for neighbor in open_neighbors(width, height, grid, coordinate) {
+            if neighbor < coordinate {
+                continue;
+            }
+
+            if connections.common_set(coordinate, neighbor) == Some(true) {
+                return false;
+            }
+
+            connections.union(coordinate, neighbor);
+            passages += 1;
+        }
+    }
+
+    // Without loops, n - 1 passages can only join n cells into a single tree
+    passages == grid.len() - 1
+}
+
+/// Returns the cells adjacent to `cell` that have no wall between them, in right, down, left, up order
+pub fn maze_neighbors(
+    width: usize,
+    height: usize,
+    grid: &[MazeNode],
+    cell: usize,
+) -> Result<Vec<usize>, MazeError> {
+    if !matches_dimensions(width, height, grid.len()) {
+        return Err(MazeError::InvalidDimensions);
+    }
+
+    if cell >= grid.len() {
+        return Err(MazeError::InvalidCell(cell));
+    }
+
+    Ok(open_neighbors(width, height, grid, cell))
+}
+
+/// Same as `maze_neighbors`, for callers that have already checked the dimensions and cell
+fn open_neighbors(width: usize, height: usize, grid: &[MazeNode], cell: usize) -> Vec<usize> {
+    let mut neighbors = Vec::with_capacity(4);
+
+    if !grid[cell].right && (cell + 1) % width != 0 {
+        neighbors.push(cell + 1);
+    }
+
+    if !grid[cell].down && cell / width + 1 < height {
+        neighbors.push(cell + width);
+    }
+
+    if cell % width != 0 && !grid[cell - 1].right {
+        neighbors.push(cell - 1);
+    }
+
+    if let Some(up) = cell.checked_sub(width) {
+        if !grid[up].down {
+            neighbors.push(up);
+        }
+    }
+
+    neighbors
+}
+
+/// Finds a shortest path from `start` to `end` with Dijkstra's algorithm, treating every passage as the same length
+///
+/// Like the solution from `create_maze`, the directions are ordered from the end of the path back to the start
+pub fn maze_shortest_path(
+    width: usize,
+    height: usize,
+    grid: &[MazeNode],
+    start: usize,
+    end: usize,
+) -> Option<Vec<MazeDirection>> {
+    if !matches_dimensions(width, height, grid.len()) || start >= grid.len() || end >= grid.len() {
+        return None;
+    }
+
+    let mut distances = vec![usize::MAX; grid.len()];
+    let mut parents = vec![None; grid.len()];
+    let mut queue = BinaryHeap::new();
+
+    distances[start] = 0;
+    queue.push(Reverse((0, start)));
+
+    while let Some(Reverse((distance, cell))) = queue.pop() {
+        if cell == end {
+            break;
+        }
+
+        if distance > distances[cell] {
+            continue;
+        }
+
+        for neighbor in open_neighbors(width, height, grid, cell) {
+            if distance + 1 < distances[neighbor] {
+                distances[neighbor] = distance + 1;
+                parents[neighbor] = Some(cell);
+                queue.push(Reverse((distance + 1, neighbor)));
+            }
+        }
+    }
+
+    if distances[end] == usize::MAX {
+        return None;
+    }
+
+    let mut solution = Vec::new();
+    let mut current = end;
+
+    while let Some(parent) = parents[current] {
+        solution.push(if current == parent + width {
+            MazeDirection::Down
+        } else if parent == current + width {
+            MazeDirection::Up
+        } else if current == parent + 1 {
+            MazeDirection::Right
+        } else {
+            MazeDirection::Left
+        });
+
+        current = parent;
+    }
+
+    Some(solution)
+}
+
+/// Walks from `start` keeping a hand on the right-hand wall until it reaches `end`, returning every step taken
+///
+/// This finds `end` in any maze without loops, but can circle forever around a loop, in which case `None` is
+/// returned. Like the solution from `create_maze`, the directions are ordered from the end of the walk back to the
+/// start
+pub fn solve_maze_wall_follow(
+    width: usize,
+    height: usize,
+    grid: &[MazeNode],
+    start: usize,
+    end: usize,
+) -> Option<Vec<MazeDirection>> {
+    const CLOCKWISE: [MazeDirection; 4] = [
+        MazeDirection::Right,
+        MazeDirection::Down,
+        MazeDirection::Left,
+        MazeDirection::Up,
+    ];
+
+    if !matches_dimensions(width, height, grid.len()) || start >= grid.len() || end >= grid.len() {
+        return None;
+    }
+
+    let mut seen = vec![[false; 4]; grid.len()];
+    let mut solution = Vec::new();
+
+    let mut current = start;
+    // Enter the maze facing down, as if through the entrance
+    let mut heading = 1;
+
+    while current != end {
+        if seen[current][heading] {
+            return None;
+        }
+        seen[current][heading] = true;
+
+        let neighbors = open_neighbors(width, height, grid, current);
+
+        // Right, straight ahead, left, then back the way it came
+        let (next, turn) = [1, 0, 3, 2].into_iter().find_map(|turn| {
+            let next = match CLOCKWISE[(heading + turn) % 4] {
+                MazeDirection::Right => current + 1,
+                MazeDirection::Down => current + width,
+                MazeDirection::Left => current.checked_sub(1)?,
+                MazeDirection::Up => current.checked_sub(width)?,
+            };
+
+            neighbors.contains(&next).then_some((next, turn))
+        })?;
+
+        heading = (heading + turn) % 4;
+        solution.push(CLOCKWISE[heading].clone());
+        current = next;
+    }
+
+    solution.reverse();
+
+    Some(solution)
+}
+
+/// Opens every wall inside each room (measured in cells), making sure each room has at least one doorway to the
+/// rest of the maze
+pub fn carve_rooms(
+    width: usize,
+    height: usize,
+    grid: &mut [MazeNode],
+    rooms: &[Rect],
+    rng: &mut impl Rng,
+) -> Result<(), MazeError> {
+    let mut cells =
+        Grid::from_vec(width, height, grid.to_vec()).ok_or(MazeError::InvalidDimensions)?;
+
+    for room in rooms {
+        let left = usize::try_from(room.left()).or(Err(MazeError::InvalidRoom))?;
+        let top = usize::try_from(room.top()).or(Err(MazeError::InvalidRoom))?;
+        let right = left + room.width() as usize;
+        let bottom = top + room.height() as usize;
+
+        if right > width || bottom > height {
+            return Err(MazeError::InvalidRoom);
+        }
+
+        let mut doorways = Vec::new();
+        let mut has_doorway = false;
+
+        for y in top..bottom {
+            for x in left..right {
+                let cell = cells.get_mut(x, y).ok_or(MazeError::InvalidRoom)?;
+
+                if x + 1 < right {
+                    cell.right = false;
+                } else if x + 1 < width {
+                    has_doorway |= !cell.right;
+                    doorways.push((x, y, MazeDirection::Right));
+                }
+
+                if y + 1 < bottom {
+                    cell.down = false;
+                } else if y + 1 < height {
+                    has_doorway |= !cell.down;
+                    doorways.push((x, y, MazeDirection::Down));
+                }
+
+                if let Some(left_cell) = x
+                    .checked_sub(1)
+                    .filter(|_| x == left)
+                    .and_then(|x| cells.get(x, y))
+                {
+                    has_doorway |= !left_cell.right;
+                    doorways.push((x - 1, y, MazeDirection::Right));
+                }
+
+                if let Some(above) = y
+                    .checked_sub(1)
+                    .filter(|_| y == top)
+                    .and_then(|y| cells.get(x, y))
+                {
+                    has_doorway |= !above.down;
+                    doorways.push((x, y - 1, MazeDirection::Down));
+                }
+            }
+        }
+
+        if !has_doorway && !doorways.is_empty() {
+            let (x, y, direction) = doorways.swap_remove(rng.gen_range(0..doorways.len()));
+
+            if let Some(cell) = cells.get_mut(x, y) {
+                match direction {
+                    MazeDirection::Right => cell.right = false,
+                    _ => cell.down = false,
+                }
+            }
+        }
+    }
+
+    grid.clone_from_slice(&cells);
+
+    if !is_connected(width, grid) {
+        return Err(MazeError::Disconnected);
+    }
+
+    Ok(())
+}
+
+/// Walls off every dead-end corridor shorter than `max_len` cells, leaving the solution from the entrance to the
+/// bottom-row exit untouched
+///
+/// Runs a single pass, so walling off spurs that meet at a junction can leave a new dead end there for the next call
+pub fn prune_short_dead_ends(
+    width: usize,
+    height: usize,
+    grid: &mut [MazeNode],
+    max_len: usize,
+) -> Result<(), MazeError> {
+    if width == 0 || height == 0 || !matches_dimensions(width, height, grid.len()) {
+        return Err(MazeError::InvalidDimensions);
+    }
+
+    let mut protected = vec![false; grid.len()];
+    protected[ENTRANCE] = true;
+
+    if let Some(exit) = (grid.len() - width..grid.len()).find(|&cell| !grid[cell].down) {
+        let solution = maze_shortest_path(width, height, grid, ENTRANCE, exit)
+            .ok_or(MazeError::Disconnected)?;
+
+        for cell in solution_cells(width, ENTRANCE, &solution) {
+            protected[cell] = true;
+        }
+    }
+
+    let mut spurs = Vec::new();
+
+    for dead_end in maze_dead_ends(width, height, grid)? {
+        if protected[dead_end] {
+            continue;
+        }
+
+        let mut spur = vec![dead_end];
+        let mut previous = dead_end;
+        let mut current = open_neighbors(width, height, grid, dead_end)[0];
+
+        while spur.len() < max_len && !protected[current] {
+            let neighbors = open_neighbors(width, height, grid, current);
+            if neighbors.len() != 2 {
+                break;
+            }
+
+            spur.push(current);
+            let next = if neighbors[0] == previous {
+                neighbors[1]
+            } else {
+                neighbors[0]
+            };
+            previous = current;
+            current = next;
+        }
+
+        if spur.len() < max_len {
+            spur.push(current);
+            spurs.push(spur);
+        }
+    }
+
+    for spur in spurs {
+        for pair in spur.windows(2) {
+            close_passage(width, grid, pair[0], pair[1]);
+        }
+    }
+
+    Ok(())
+}
+
+/// Puts back the wall between two adjacent cells
+fn close_passage(width: usize, grid: &mut [MazeNode], a: usize, b: usize) {
+    let (first, second) = (a.min(b), a.max(b));
+
+    if second == first + width {
+        grid[first].down = true;
+    } else {
+        grid[first].right = true;
+    }
+}
+
+fn is_connected(width: usize, grid: &[MazeNode]) -> bool {
+    let mut connections = DisjointSet::with_size(grid.len());
+
+    for (coordinate, node) in grid.iter().enumerate() {
+        if !node.right && (coordinate + 1) % width != 0 {
+            connections.union(coordinate, coordinate + 1);
+        }
+
+        if !node.down && coordinate + width < grid.len() {
+            connections.union(coordinate, coordinate + width);
+        }
+    }
+
+    connections.compress_all();
+
+    (0..grid.len()).all(|coordinate| connections.common_set(0, coordinate) == Some(true))
+}
+
+#[cfg(test)]
+mod tests {
+    use imageproc::rect::Rect;
+    use rand::{thread_rng, RngCore};
+
+    use crate::{
+        test_util::assert_png_snapshot,
+        util::{BLACK_PIXEL, GREEN_PIXEL, LIGHT_GRAY_PIXEL, RED_PIXEL, WHITE_PIXEL},
+    };
+
+    use super::{
+        GenStats, MazeAlgorithm, MazeDirection, MazeError, MazeEvent, MazeGenerator, MazeNode,
+        MazeRenderOptions,
+    };
+
+    const THIN_IMAGE: &[u8] = include_bytes!("../../../tests/maze/thin.png");
+
+    #[test]
+    fn instrumented_events() {
+        let mut events = Vec::new();
+        let (grid, _) =
+            super::create_maze_instrumented(6, 4, MazeAlgorithm::RecursiveBacktrack, |event| {
+                events.push(event)
+            })
+            .expect("should be ok");
+
+        assert_eq!(events[0], MazeEvent::Visit(0));
+
+        let count = |f: fn(&MazeEvent) -> bool| events.iter().filter(|&event| f(event)).count();
+        assert_eq!(count(|event| matches!(event, MazeEvent::Visit(_))), 24);
+        assert_eq!(count(|event| matches!(event, MazeEvent::Carve { .. })), 23);
+        assert_eq!(count(|event| matches!(event, MazeEvent::Backtrack(_))), 24);
+
+        for event in &events {
+            if let &MazeEvent::Carve { from, to } = event {
+                assert!(super::maze_neighbors(6, 4, &grid, from)
+                    .expect("should be ok")
+                    .contains(&to));
+            }
+        }
+    }
+
+    #[test]
+    fn stats() {
+        let (_, _, stats) = super::create_maze_with_stats(6, 4, MazeAlgorithm::RecursiveBacktrack)
+            .expect("should be ok");
+        assert_eq!(stats.cells_visited, 24);
+        assert_eq!(stats.backtracks, 24);
+        assert!((1..=24).contains(&stats.max_stack_depth));
+
+        let (_, _, stats) = super::create_maze_with_stats(1, 5, MazeAlgorithm::RecursiveBacktrack)
+            .expect("should be ok");
+        assert_eq!(
+            stats,
+            GenStats {
+                cells_visited: 5,
+                backtracks: 5,
+                max_stack_depth: 5,
+            }
+        );
+    }
+
+    #[test]
+    fn loops() {
+        let (grid, solution, added) =
+            super::create_maze_with_loops(8, 6, MazeAlgorithm::RecursiveBacktrack, 5)
+                .expect("should be ok");
+        assert_eq!(added, 5);
+
+        let passages = (0..grid.len())
+            .map(|cell| {
+                super::maze_neighbors(8, 6, &grid, cell)
+                    .expect("should be ok")
+                    .len()
+            })
+            .sum::<usize>()
+            / 2;
+        assert_eq!(passages, 8 * 6 - 1 + 5);
+        assert!(!super::is_perfect_maze(8, 6, &grid));
+
+        super::validate_solution(8, 6, &grid, 0, &solution).expect("should be ok");
+    }
+
+    #[test]
+    fn loops_run_out_of_walls() {
+        let (_, _, added) =
+            super::create_maze_with_loops(2, 2, MazeAlgorithm::RecursiveBacktrack, 10)
+                .expect("should be ok");
+        assert_eq!(added, 1);
+    }
+
+    #[test]
+    fn markers() {
+        let grid = vec![MazeNode::new(); 4];
+        let options = MazeRenderOptions {
+            start: Some(0),
+            end: Some(3),
+            ..Default::default()
+        };
+
+        let image = super::print_maze_with_options(2, 2, &grid, &options).expect("should be ok");
+        assert_eq!(*image.get_pixel(5, 5), GREEN_PIXEL);
+        assert_eq!(*image.get_pixel(15, 15), RED_PIXEL);
+        assert_eq!(*image.get_pixel(15, 5), WHITE_PIXEL);
+    }
+
+    #[test]
+    fn markers_out_of_bounds() {
+        let grid = vec![MazeNode::new(); 4];
+        let options = MazeRenderOptions {
+            end: Some(4),
+            ..Default::default()
+        };
+
+        let actual = super::print_maze_with_options(2, 2, &grid, &options);
+        assert!(matches!(actual, Err(MazeError::InvalidCell(4))));
+    }
+
+    #[test]
+    fn thick_border() {
+        let (width, height, grid) = super::maze_from_ascii(
+            "+  +--+\n\
+             |     |\n\
+             +--+  +\n",
+        )
+        .expect("should be ok");
+
+        let options = MazeRenderOptions {
+            border_thickness: 3,
+            ..Default::default()
+        };
+
+        let image =
+            super::print_maze_with_options(width, height, &grid, &options).expect("should be ok");
+        assert_eq!(image.dimensions(), (21, 11));
+
+        for (x, y) in [
+            (0, 0),
+            (2, 2),
+            (20, 10),
+            (18, 8),
+            (2, 5),
+            (18, 5),
+            (5, 8),
+            (12, 2),
+        ] {
+            assert_eq!(*image.get_pixel(x, y), BLACK_PIXEL, "({x}, {y})");
+        }
+
+        // entrance above the first cell and exit below the last stay open
+        assert_eq!(*image.get_pixel(5, 0), WHITE_PIXEL);
+        assert_eq!(*image.get_pixel(15, 10), WHITE_PIXEL);
+        assert_eq!(*image.get_pixel(10, 5), WHITE_PIXEL);
+    }
+
+    #[test]
+    fn border_corners() {
+        let (grid, _) =
+            super::create_maze(6, 5, MazeAlgorithm::RecursiveBacktrack).expect("should be ok");
+
+        let image = super::print_maze(6, 5, &grid).expect("should be ok");
+        for (x, y) in [(0, 0), (0, 50), (60, 0), (60, 50), (10, 0), (0, 9)] {
+            assert_eq!(*image.get_pixel(x, y), BLACK_PIXEL, "({x}, {y})");
+        }
+
+        // only the entrance cell's span is open along the top
+        for x in 1..=9 {
+            assert_eq!(*image.get_pixel(x, 0), WHITE_PIXEL);
+        }
+        assert_eq!(*image.get_pixel(11, 0), BLACK_PIXEL);
+    }
+
+    #[test]
+    fn custom_generator() {
+        // Serpentine: every row is a corridor, joined alternately at its right and left ends
+        struct Serpentine;
+
+        impl MazeGenerator for Serpentine {
+            fn generate(&self, width: usize, height: usize, _: &mut dyn RngCore) -> Vec<MazeNode> {
+                (0..width * height)
+                    .map(|cell| {
+                        let (x, y) = (cell % width, cell / width);
+                        let turn = if y % 2 == 0 { width - 1 } else { 0 };
+                        MazeNode::with_walls(x == width - 1, x != turn || y == height - 1)
+                    })
+                    .collect()
+            }
+        }
+
+        let (grid, solution) =
+            super::create_maze_with_generator(4, 3, &Serpentine).expect("should be ok");
+        assert_eq!(solution.len(), 11);
+        super::validate_solution(4, 3, &grid, 0, &solution).expect("should be ok");
+        assert!(!grid[11].down);
+        assert!(super::is_perfect_maze(4, 3, &grid));
+
+        struct Walled;
+
+        impl MazeGenerator for Walled {
+            fn generate(&self, width: usize, height: usize, _: &mut dyn RngCore) -> Vec<MazeNode> {
+                vec![MazeNode::new(); width * height]
+            }
+        }
+
+        let actual = super::create_maze_with_generator(4, 3, &Walled);
+        assert!(matches!(actual, Err(MazeError::Disconnected)));
+    }
+
+    #[test]
+    fn perfect_maze() {
+        for (width, height) in [(1, 1), (1, 6), (6, 1), (10, 7)] {
+            for algorithm in [
+                MazeAlgorithm::RecursiveBacktrack,
+                MazeAlgorithm::RecursiveBacktrackBiased(0.8),
+            ] {
+                let (grid, _) = super::create_maze(width, height, algorithm).expect("should be ok");
+                assert!(super::is_perfect_maze(width, height, &grid));
+            }
+        }
+
+        let open = vec![MazeNode::with_walls(false, false); 4];
+        assert!(!super::is_perfect_maze(2, 2, &open));
+
+        let walled = vec![MazeNode::new(); 4];
+        assert!(!super::is_perfect_maze(2, 2, &walled));
+        assert!(!super::is_perfect_maze(3, 2, &walled));
+    }
+
+    #[test]
+    fn create_masked() {
+        // A ring around a blocked center, with the top right corner also blocked
+        let mask = [
+            true, true, true, false, //
+            true, false, false, true, //
+            true, false, false, true, //
+            true, true, true, true,
+        ];
+
+        let (grid, solution) =
+            super::create_maze_masked(4, 4, &mask, MazeAlgorithm::RecursiveBacktrack)
+                .expect("should be ok");
+
+        let mut passages = 0;
+        for (cell, &open) in mask.iter().enumerate() {
+            let neighbors = super::maze_neighbors(4, 4, &grid, cell).expect("should be ok");
+            assert!(open || neighbors.is_empty());
+            assert!(neighbors.iter().all(|&neighbor| mask[neighbor]));
+            passages += neighbors.len();
+        }
+        assert_eq!(passages / 2, 10);
+
+        super::validate_solution(4, 4, &grid, 0, &solution).expect("should be ok");
+        let end = *super::solution_cells(4, 0, &solution)
+            .last()
+            .expect("should be some");
+        assert_eq!(end / 4, 3);
+        assert!(!grid[end].down);
+    }
+
+    #[test]
+    fn create_masked_invalid() {
+        let split = [true, false, true, true, false, true];
+        let actual = super::create_maze_masked(3, 2, &split, MazeAlgorithm::RecursiveBacktrack);
+        assert!(matches!(actual, Err(MazeError::Disconnected)));
+
+        for mask in [&[true; 5][..], &[false; 6][..]] {
+            let actual = super::create_maze_masked(3, 2, mask, MazeAlgorithm::RecursiveBacktrack);
+            assert!(matches!(actual, Err(MazeError::InvalidDimensions)));
+        }
+
+        let corner = [false, true, true, true, true, true];
+        let actual = super::create_maze_masked(3, 2, &corner, MazeAlgorithm::RecursiveBacktrack);
+        assert!(matches!(actual, Err(MazeError::InvalidCell(0))));
+    }
+
+    #[test]
+    fn create_with_ends() {
+        let maze = super::create_maze_with_ends(9, 5, MazeAlgorithm::RecursiveBacktrack)
+            .expect("should be ok");
+
+        assert_eq!(maze.start, 0);
+        assert_eq!(maze.end / 9, 4);
+        assert!(!maze.grid[maze.end].down);
+
+        let cells = super::solution_cells(9, maze.start, &maze.solution);
+        assert_eq!(cells.last(), Some(&maze.end));
+    }
+
+    #[test]
+    fn oversized_dimensions() {
+        let grid = vec![MazeNode::new(); 2];
+
+        for actual in [
+            super::print_maze(usize::MAX, 2, &grid).map(|_| ()),
+            super::print_maze_thin(usize::MAX, 2, &grid).map(|_| ()),
+            super::maze_to_ascii(usize::MAX, 2, &grid).map(|_| ()),
+            super::maze_dead_ends(usize::MAX, 2, &grid).map(|_| ()),
+            super::validate_solution(usize::MAX, 2, &grid, 0, &[]),
+            super::prune_short_dead_ends(usize::MAX, 2, &mut grid.clone(), 1),
+            super::create_maze_with_exit(usize::MAX, 2, MazeAlgorithm::RecursiveBacktrack, 0)
+                .map(|_| ()),
+        ] {
+            assert!(matches!(actual, Err(MazeError::InvalidDimensions)));
+        }
+
+        assert!(!super::is_perfect_maze(usize::MAX, 2, &grid));
+        assert_eq!(super::solve_to_any(usize::MAX, 2, &grid, 0, &[1]), None);
+        assert_eq!(super::maze_shortest_path(usize::MAX, 2, &grid, 0, 1), None);
+    }
+
+    #[test]
+    fn create_empty() {
+        for (width, height) in [(0, 0), (0, 3), (3, 0), (usize::MAX, 2)] {
+            let actual = super::create_maze(width, height, MazeAlgorithm::RecursiveBacktrack);
+            assert!(matches!(actual, Err(MazeError::InvalidDimensions)));
+        }
+    }
+
+    #[test]
+    fn create_with_exit() {
+        let (grid, solution) =
+            super::create_maze_with_exit(7, 4, MazeAlgorithm::RecursiveBacktrack, 22)
+                .expect("should be ok");
+
+        let exits = (21..28)
+            .filter(|&cell| !grid[cell].down)
+            .collect::<Vec<_>>();
+        assert_eq!(exits, vec![22]);
+
+        super::validate_solution(7, 4, &grid, 0, &solution).expect("should be ok");
+        assert_eq!(
+            super::maze_shortest_path(7, 4, &grid, 0, 22),
+            Some(solution)
+        );
+    }
+
+    #[test]
+    fn create_with_exit_outside_bottom_row() {
+        for exit in [20, 28] {
+            let actual =
+                super::create_maze_with_exit(7, 4, MazeAlgorithm::RecursiveBacktrack, exit);
+            assert!(matches!(actual, Err(MazeError::InvalidCell(cell)) if cell == exit));
+        }
+    }
+
+    #[test]
+    fn solution_cells() {
+        let solution = [
+            MazeDirection::Down,
+            MazeDirection::Right,
+            MazeDirection::Down,
+            MazeDirection::Right,
+        ];
+
+        assert_eq!(super::solution_cells(3, 0, &solution), vec![0, 1, 4, 5, 8]);
+        assert_eq!(super::solution_cells(3, 7, &[]), vec![7]);
+    }
+
+    #[test]
+    fn solution_waypoints() {
+        let (width, height, grid) = super::maze_from_ascii(
+            "+  +--+--+\n\
+             |     |  |\n\
+             +--+  +  +\n\
+             |        |\n\
+             +--+--+  +\n",
+        )
+        .expect("should be ok");
+
+        let solution =
+            super::maze_shortest_path(width, height, &grid, 0, 5).expect("should be some");
+
+        assert_eq!(
+            super::solution_waypoints(width, 0, &solution),
+            vec![(0, 0), (1, 0), (1, 1), (2, 1)]
+        );
+    }
+
+    #[test]
+    fn solution_svg() {
+        let (width, height, grid) = super::maze_from_ascii(
+            "+  +--+\n\
+             |     |\n\
+             +--+  +\n",
+        )
+        .expect("should be ok");
+        let solution = vec![MazeDirection::Right];
+
+        let actual = super::maze_solution_to_svg(width, height, &grid, 0, &solution, 10)
+            .expect("should be ok");
+        assert_eq!(
+            actual,
+            r#"<polyline points="5,0 5,5 15,5 15,10" fill="none" stroke="red" stroke-width="2.5"/>"#
+        );
+
+        let actual =
+            super::maze_solution_to_svg(width, height, &grid, 1, &[], 10).expect("should be ok");
+        assert_eq!(
+            actual,
+            r#"<polyline points="15,5 15,10" fill="none" stroke="red" stroke-width="2.5"/>"#
+        );
+
+        let actual =
+            super::maze_solution_to_svg(width, height, &grid, 0, &[MazeDirection::Down], 10);
+        assert!(matches!(actual, Err(MazeError::InvalidSolution(0))));
+    }
+
+    #[test]
+    fn animate_solve() {
+        let (width, height, grid) =
+            super::maze_from_ascii("+  +--+--+\n|        |\n+--+--+  +\n").expect("should be ok");
+
+        let frames = super::animate_maze_solve(width, height, &grid, 0, 2).expect("should be ok");
+        assert_eq!(frames.len(), 4);
+
+        let unsolved = super::print_maze(width, height, &grid).expect("should be ok");
+        for frame in &frames {
+            assert_eq!(frame.dimensions(), unsolved.dimensions());
+        }
+
+        assert_eq!(*frames[0].get_pixel(5, 5), GREEN_PIXEL);
+        assert_eq!(*frames[0].get_pixel(15, 5), WHITE_PIXEL);
+        assert_eq!(*frames[1].get_pixel(5, 5), LIGHT_GRAY_PIXEL);
+        assert_eq!(*frames[1].get_pixel(15, 5), GREEN_PIXEL);
+        assert_eq!(*frames[2].get_pixel(25, 5), GREEN_PIXEL);
+        assert_eq!(*frames[2].get_pixel(10, 5), WHITE_PIXEL);
+
+        for x in 5..=25 {
+            assert_eq!(*frames[3].get_pixel(x, 5), RED_PIXEL);
+        }
+        assert_eq!(*frames[3].get_pixel(5, 3), LIGHT_GRAY_PIXEL);
+    }
+
+    #[test]
+    fn animate_solve_disconnected() {
+        let grid = vec![MazeNode::new(); 4];
+
+        let actual = super::animate_maze_solve(2, 2, &grid, 0, 3).expect_err("should be Err");
+        assert!(matches!(actual, MazeError::Disconnected));
+
+        let actual = super::animate_maze_solve(2, 2, &grid, 0, 4).expect_err("should be Err");
+        assert!(matches!(actual, MazeError::InvalidCell(4)));
+    }
+
+    #[test]
+    fn print_explored() {
+        let (grid, _) =
+            super::create_maze(4, 3, MazeAlgorithm::RecursiveBacktrack).expect("should be ok");
+        let visited = (0..12).map(|cell| cell % 2 == 0).collect::<Vec<_>>();
+
+        let actual =
+            super::print_maze_explored(4, 3, &grid, &visited, GREEN_PIXEL).expect("should be ok");
+        let unsolved = super::print_maze(4, 3, &grid).expect("should be ok");
+        assert_eq!(actual.dimensions(), unsolved.dimensions());
+
+        for (cell, &visited) in visited.iter().enumerate() {
+            let x = (cell % 4) as u32 * 10;
+            let y = (cell / 4) as u32 * 10;
+            let expected = if visited { GREEN_PIXEL } else { WHITE_PIXEL };
+
+            assert_eq!(*actual.get_pixel(x + 5, y + 5), expected);
+            assert_eq!(*actual.get_pixel(x, y), *unsolved.get_pixel(x, y));
+        }
+
+        let actual = super::print_maze_explored(4, 3, &grid, &visited[1..], GREEN_PIXEL)
+            .expect_err("should be Err");
+        assert!(matches!(actual, MazeError::InvalidDimensions));
+    }
+
+    #[test]
+    fn validate_solution() {
+        let (grid, solution) =
+            super::create_maze(9, 7, MazeAlgorithm::RecursiveBacktrack).expect("should be ok");
+        super::validate_solution(9, 7, &grid, 0, &solution).expect("should be ok");
+    }
+
+    #[test]
+    fn validate_solution_through_wall() {
+        let (width, height, grid) = super::maze_from_ascii(
+            "+  +--+\n\
+             |  |  |\n\
+             +  +  +\n\
+             |     |\n\
+             +--+--+\n",
+        )
+        .expect("should be ok");
+
+        let solution = [MazeDirection::Up, MazeDirection::Right, MazeDirection::Down];
+        super::validate_solution(width, height, &grid, 0, &solution).expect("should be ok");
+
+        let solution = [MazeDirection::Down, MazeDirection::Right];
+        let actual = super::validate_solution(width, height, &grid, 0, &solution);
+        assert!(matches!(actual, Err(MazeError::InvalidSolution(1))));
+    }
+
+    #[test]
+    fn print_solution_through_wall() {
+        let grid = vec![MazeNode::new(); 4];
+        let image = super::print_maze(2, 2, &grid).expect("should be ok");
+
+        let actual = super::print_maze_solution(image, &grid, &[MazeDirection::Right]);
+        assert!(matches!(actual, Err(MazeError::InvalidSolution(0))));
+    }
+
+    #[test]
+    fn print_empty() {
+        for (width, height) in [(5, 0), (0, 5), (0, 0)] {
+            let options = MazeRenderOptions {
+                border_thickness: 4,
+                ..MazeRenderOptions::default()
+            };
+
+            super::print_maze_with_options(width, height, &[], &options).expect("should be ok");
+        }
+    }
+
+    #[test]
+    fn print_thin_empty() {
+        for (width, height) in [(5, 0), (0, 5), (0, 0)] {
+            let actual = super::print_maze_thin(width, height, &[]).expect("should be ok");
+            assert!(actual.pixels().all(|&pixel| pixel == BLACK_PIXEL));
+        }
+    }
+
+    #[test]
+    fn print_solution_color() {
+        let (grid, solution) =
+            super::create_maze(5, 4, MazeAlgorithm::RecursiveBacktrack).expect("should be ok");
+        let unsolved = super::print_maze(5, 4, &grid).expect("should be ok");
+
+        let actual = super::print_maze_solution_with_color(unsolved, &grid, &solution, GREEN_PIXEL)
+            .expect("should be ok");
+
+        assert_eq!(*actual.get_pixel(5, 0), GREEN_PIXEL);
+        assert!(actual.pixels().all(|&pixel| pixel != RED_PIXEL));
+    }
+
+    #[test]
+    fn print_solved() {
+        let (grid, solution) =
+            super::create_maze(6, 5, MazeAlgorithm::RecursiveBacktrack).expect("should be ok");
+
+        let expected = super::print_maze_solution(
+            super::print_maze(6, 5, &grid).expect("should be ok"),
+            &grid,
+            &solution,
+        )
+        .expect("should be ok");
+
+        let actual = super::print_maze_solved(6, 5, &grid, &solution).expect("should be ok");
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn print_too_large() {
+        let actual = super::pixel_length(usize::MAX / 4, 10);
+        assert!(matches!(actual, Err(MazeError::TooLarge)));
+
+        assert_eq!(super::pixel_length(12, 10).expect("should be ok"), 120);
+    }
+
+    #[test]
+    fn print_thin() {
+        let (width, height, grid) = super::maze_from_ascii(
+            "+  +--+--+\n\
+             |     |  |\n\
+             +--+  +  +\n\
+             |        |\n\
+             +--+--+  +\n",
+        )
+        .expect("should be ok");
+
+        let actual = super::print_maze_thin(width, height, &grid).expect("should be ok");
+        assert_png_snapshot(&actual, THIN_IMAGE);
+    }
+
+    #[test]
+    fn bitmask() {
+        let grid = vec![
+            MazeNode {
+                right: false,
+                down: true,
+            },
+            MazeNode {
+                right: true,
+                down: true,
+            },
+        ];
+
+        let actual = super::maze_to_bitmask(2, 1, &grid).expect("should be ok");
+        assert_eq!(actual, vec![0b1100, 0b0111]);
+    }
+
+    #[test]
+    fn ascii() {
+        const ASCII: &str = "+  +--+\n|     |\n+--+  +\n";
+
+        let grid = vec![
+            MazeNode {
+                right: false,
+                down: true,
+            },
+            MazeNode {
+                right: true,
+                down: false,
+            },
+        ];
+
+        assert_eq!(
+            super::maze_to_ascii(2, 1, &grid).expect("should be ok"),
+            ASCII
+        );
+        assert_eq!(
+            super::maze_from_ascii(ASCII).expect("should be ok"),
+            (2, 1, grid)
+        );
+    }
+
+    #[test]
+    fn ascii_round_trip() {
+        let (grid, _) =
+            super::create_maze(8, 5, MazeAlgorithm::RecursiveBacktrack).expect("should be ok");
+
+        let ascii = super::maze_to_ascii(8, 5, &grid).expect("should be ok");
+        let actual = super::maze_from_ascii(&ascii).expect("should be ok");
+        assert_eq!(actual, (8, 5, grid));
+    }
+
+    #[test]
+    fn ascii_ragged() {
+        let actual =
+            super::maze_from_ascii("+  +--+\n|    |\n+--+  +\n").expect_err("should be Err");
+        assert!(matches!(actual, super::MazeError::InvalidDimensions));
+    }
+
+    #[test]
+    fn bitmask_overflow() {
+        let actual = super::bitmask_to_maze(usize::MAX, 2, &[0; 2]).expect_err("should be Err");
+        assert!(matches!(actual, super::MazeError::InvalidDimensions));
+    }
+
+    #[test]
+    fn dead_ends() {
+        let grid = super::maze_from_ascii(
+            "+  +--+--+\n\
+             |        |\n\
+             +--+--+  +\n\
+             |        |\n\
+             +--+--+--+\n",
+        )
+        .expect("should be ok")
+        .2;
+
+        let actual = super::maze_dead_ends(3, 2, &grid).expect("should be ok");
+        assert_eq!(actual, vec![0, 3]);
+    }
+
+    #[test]
+    fn neighbors() {
+        let (width, height, grid) = super::maze_from_ascii(
+            "+  +--+--+\n\
+             |  |     |\n\
+             +  +--+  +\n\
+             |        |\n\
+             +--+--+--+\n",
+        )
+        .expect("should be ok");
+
+        assert_eq!(
+            super::maze_neighbors(width, height, &grid, 0).expect("should be ok"),
+            vec![3]
+        );
+        assert_eq!(
+            super::maze_neighbors(width, height, &grid, 1).expect("should be ok"),
+            vec![2]
+        );
+        assert_eq!(
+            super::maze_neighbors(width, height, &grid, 4).expect("should be ok"),
+            vec![5, 3]
+        );
+        assert_eq!(
+            super::maze_neighbors(width, height, &grid, 5).expect("should be ok"),
+            vec![4, 2]
+        );
+
+        let actual = super::maze_neighbors(width, height, &grid, 6);
+        assert!(matches!(actual, Err(MazeError::InvalidCell(6))));
+
+        for (width, height) in [(0, 6), (2, 2)] {
+            let actual = super::maze_neighbors(width, height, &grid, 0);
+            assert!(matches!(actual, Err(MazeError::InvalidDimensions)));
+        }
+    }
+
+    #[test]
+    fn shortest_path_matches_bfs() {
+        let (grid, expected) =
+            super::create_maze(12, 9, MazeAlgorithm::RecursiveBacktrack).expect("should be ok");
+        let exit = (12 * 8..12 * 9)
+            .find(|&cell| !grid[cell].down)
+            .expect("maze should have an exit");
+
+        let actual = super::maze_shortest_path(12, 9, &grid, 0, exit);
+        assert_eq!(actual, Some(expected));
+    }
+
+    #[test]
+    fn shortest_path_disconnected() {
+        let grid = vec![MazeNode::new(); 4];
+        assert_eq!(super::maze_shortest_path(2, 2, &grid, 0, 3), None);
+    }
+
+    #[test]
+    fn wall_follow() {
+        let (grid, shortest) =
+            super::create_maze(12, 9, MazeAlgorithm::RecursiveBacktrack).expect("should be ok");
+        let exit = (12 * 8..12 * 9)
+            .find(|&cell| !grid[cell].down)
+            .expect("maze should have an exit");
+
+        let actual = super::solve_maze_wall_follow(12, 9, &grid, 0, exit).expect("should be some");
+        super::validate_solution(12, 9, &grid, 0, &actual).expect("should be ok");
+        assert_eq!(super::solution_cells(12, 0, &actual).last(), Some(&exit));
+        assert!(actual.len() >= shortest.len());
+    }
+
+    #[test]
+    fn wall_follow_misses_island() {
+        let grid = vec![MazeNode::with_walls(false, false); 9];
+
+        assert_eq!(super::solve_maze_wall_follow(3, 3, &grid, 0, 4), None);
+        assert!(super::maze_shortest_path(3, 3, &grid, 0, 4).is_some());
+        assert_eq!(
+            super::solve_maze_wall_follow(3, 3, &grid, 0, 8),
+            Some(vec![
+                MazeDirection::Right,
+                MazeDirection::Right,
+                MazeDirection::Down,
+                MazeDirection::Down
+            ])
+        );
+    }
+
+    #[test]
+    fn solve_to_nearest_goal() {
+        let (width, height, grid) = super::maze_from_ascii(
+            "+  +--+--+\n\
+             |        |\n\
+             +--+--+  +\n\
+             |        |\n\
+             +--+--+--+\n",
+        )
+        .expect("should be ok");
+
+        let actual = super::solve_to_any(width, height, &grid, 0, &[3, 4]);
+        assert_eq!(
+            actual,
+            Some((
+                4,
+                vec![
+                    MazeDirection::Left,
+                    MazeDirection::Down,
+                    MazeDirection::Right,
+                    MazeDirection::Right
+                ]
+            ))
+        );
+
+        assert_eq!(super::solve_to_any(width, height, &grid, 0, &[]), None);
+    }
+
+    #[test]
+    fn carve_rooms() {
+        let (mut grid, _) =
+            super::create_maze(10, 10, MazeAlgorithm::RecursiveBacktrack).expect("should be ok");
+        let room = Rect::at(2, 3).of_size(4, 3);
+
+        super::carve_rooms(10, 10, &mut grid, &[room], &mut thread_rng()).expect("should be ok");
+
+        for y in 3..6 {
+            for x in 2..6 {
+                assert!(x == 5 || !grid[y * 10 + x].right);
+                assert!(y == 5 || !grid[y * 10 + x].down);
+            }
+        }
+    }
+
+    #[test]
+    fn carve_rooms_out_of_bounds() {
+        let (mut grid, _) =
+            super::create_maze(10, 10, MazeAlgorithm::RecursiveBacktrack).expect("should be ok");
+        let room = Rect::at(8, 8).of_size(3, 2);
+
+        let actual = super::carve_rooms(10, 10, &mut grid, &[room], &mut thread_rng())
+            .expect_err("should be Err");
+        assert!(matches!(actual, super::MazeError::InvalidRoom));
+    }
+
+    #[test]
+    fn prune_short_dead_ends() {
+        let (width, height, mut grid) = super::maze_from_ascii(
+            "+  +--+--+\n\
+             |  |     |\n\
+             +  +  +--+\n\
+             |  |  |  |\n\
+             +  +  +  +\n\
+             |        |\n\
+             +--+--+  +\n",
+        )
+        .expect("should be ok");
+        let solution = super::maze_shortest_path(width, height, &grid, 0, 8);
+
+        super::prune_short_dead_ends(width, height, &mut grid, 2).expect("should be ok");
+
+        assert!(grid[5].down);
+        assert!(!grid[1].right && !grid[1].down && !grid[4].down);
+        assert_eq!(
+            super::maze_dead_ends(width, height, &grid).expect("should be ok"),
+            vec![0, 2, 8]
+        );
+        assert_eq!(
+            super::maze_shortest_path(width, height, &grid, 0, 8),
+            solution
+        );
+    }
+
+    #[test]
+    fn prune_empty() {
+        for (width, height) in [(5, 0), (0, 5), (0, 0)] {
+            let actual = super::prune_short_dead_ends(width, height, &mut [], 2);
+            assert!(matches!(actual, Err(MazeError::InvalidDimensions)));
+        }
+    }
+
+    #[test]
+    fn prune_y_shaped_spurs() {
+        // The entrance runs straight down the left column, with a stem off its middle splitting into two spurs
+        let mut grid = vec![
+            MazeNode::with_walls(true, false),
+            MazeNode::with_walls(true, false),
+            MazeNode::with_walls(true, true),
+            MazeNode::with_walls(false, false),
+            MazeNode::with_walls(false, true),
+            MazeNode::with_walls(true, true),
+            MazeNode::with_walls(false, false),
+            MazeNode::with_walls(false, true),
+            MazeNode::with_walls(true, true),
+        ];
+
+        super::prune_short_dead_ends(3, 3, &mut grid, 2).expect("should be ok");
+        assert_eq!(
+            super::maze_dead_ends(3, 3, &grid).expect("should be ok"),
+            vec![0, 4, 8]
+        );
+
+        super::prune_short_dead_ends(3, 3, &mut grid, 2).expect("should be ok");
+        assert_eq!(
+            super::maze_dead_ends(3, 3, &grid).expect("should be ok"),
+            vec![0, 8]
+        );
+    }
+
+    #[test]
+    fn prune_keeps_solution() {
+        let (mut grid, solution) =
+            super::create_maze(12, 9, MazeAlgorithm::RecursiveBacktrack).expect("should be ok");
+
+        super::prune_short_dead_ends(12, 9, &mut grid, 4).expect("should be ok");
+        super::validate_solution(12, 9, &grid, 0, &solution).expect("should be ok");
+    }
+
+    #[test]
+    fn bitmask_round_trip() {
+        let (grid, _) =
+            super::create_maze(8, 5, MazeAlgorithm::RecursiveBacktrack).expect("should be ok");
+
+        let bitmask = super::maze_to_bitmask(8, 5, &grid).expect("should be ok");
+        let actual = super::bitmask_to_maze(8, 5, &bitmask).expect("should be ok");
+        assert_eq!(actual, grid);
+    }
 }
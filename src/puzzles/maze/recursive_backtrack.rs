@@ -1,12 +1,115 @@
-use crate::{structures::disjoint_set::DisjointSet, util::choose_random};
+use rand::RngCore;
+
+use crate::{
+    structures::disjoint_set::DisjointSet,
+    util::{choose_random, choose_weighted},
+};
+
+use super::{MazeDirection, MazeEvent, MazeGenerator, MazeNode};
+
+pub struct RecursiveBacktrack;
+
+impl RecursiveBacktrack {
+    /// Carves only between cells marked `true` in 'mask', starting from the first of them
+    pub fn carve(
+        &self,
+        width: usize,
+        height: usize,
+        mask: &[bool],
+        rng: &mut dyn RngCore,
+        on_event: &mut dyn FnMut(MazeEvent),
+    ) -> Vec<MazeNode> {
+        generate(
+            width,
+            height,
+            mask,
+            |visitable| choose_random(visitable, rng),
+            on_event,
+        )
+    }
+}
 
-use super::{MazeDirection, MazeNode};
+impl MazeGenerator for RecursiveBacktrack {
+    fn generate(&self, width: usize, height: usize, rng: &mut dyn RngCore) -> Vec<MazeNode> {
+        self.generate_instrumented(width, height, rng, &mut |_| {})
+    }
+
+    fn generate_instrumented(
+        &self,
+        width: usize,
+        height: usize,
+        rng: &mut dyn RngCore,
+        on_event: &mut dyn FnMut(MazeEvent),
+    ) -> Vec<MazeNode> {
+        self.carve(width, height, &vec![true; width * height], rng, on_event)
+    }
+}
+
+/// The wrapped value is the relative weight (0 to 1) given to horizontal moves, with 0.5 being unbiased
+pub struct RecursiveBacktrackBiased(pub f32);
+
+impl RecursiveBacktrackBiased {
+    /// Carves only between cells marked `true` in 'mask', starting from the first of them
+    pub fn carve(
+        &self,
+        width: usize,
+        height: usize,
+        mask: &[bool],
+        rng: &mut dyn RngCore,
+        on_event: &mut dyn FnMut(MazeEvent),
+    ) -> Vec<MazeNode> {
+        let horizontal_bias = self.0.clamp(0.0, 1.0);
+
+        generate(
+            width,
+            height,
+            mask,
+            |visitable| {
+                choose_weighted(
+                    visitable,
+                    |direction| match direction {
+                        MazeDirection::Right | MazeDirection::Left => horizontal_bias,
+                        MazeDirection::Down | MazeDirection::Up => 1.0 - horizontal_bias,
+                    },
+                    rng,
+                )
+            },
+            on_event,
+        )
+    }
+}
 
-pub fn recursive_backtrack(width: usize, height: usize) -> Vec<MazeNode> {
+impl MazeGenerator for RecursiveBacktrackBiased {
+    fn generate(&self, width: usize, height: usize, rng: &mut dyn RngCore) -> Vec<MazeNode> {
+        self.generate_instrumented(width, height, rng, &mut |_| {})
+    }
+
+    fn generate_instrumented(
+        &self,
+        width: usize,
+        height: usize,
+        rng: &mut dyn RngCore,
+        on_event: &mut dyn FnMut(MazeEvent),
+    ) -> Vec<MazeNode> {
+        self.carve(width, height, &vec![true; width * height], rng, on_event)
+    }
+}
+
+fn generate(
+    width: usize,
+    height: usize,
+    mask: &[bool],
+    mut choose: impl FnMut(&mut Vec<MazeDirection>) -> Option<MazeDirection>,
+    mut on_event: impl FnMut(MazeEvent),
+) -> Vec<MazeNode> {
     let mut maze = vec![MazeNode::new(); width * height];
     let mut connections = DisjointSet::with_size(width * height);
 
-    let mut path = vec![0];
+    let Some(start) = mask.iter().position(|&cell| cell) else {
+        return maze;
+    };
+
+    let mut path = vec![start];
     let mut can_visit = vec![
         vec![
             MazeDirection::Right,
@@ -16,19 +119,30 @@ pub fn recursive_backtrack(width: usize, height: usize) -> Vec<MazeNode> {
         ];
         width * height
     ];
+    on_event(MazeEvent::Visit(start));
+
     while !path.is_empty() {
         let coordinate = path[path.len() - 1];
 
         match visit_next(
             coordinate,
             width,
-            height,
+            mask,
             &mut maze,
             &mut connections,
             &mut can_visit[coordinate],
+            &mut choose,
         ) {
-            Some(next) => path.push(next),
+            Some(next) => {
+                on_event(MazeEvent::Carve {
+                    from: coordinate,
+                    to: next,
+                });
+                on_event(MazeEvent::Visit(next));
+                path.push(next);
+            }
             None => {
+                on_event(MazeEvent::Backtrack(coordinate));
                 path.pop();
             }
         };
@@ -40,13 +154,14 @@ pub fn recursive_backtrack(width: usize, height: usize) -> Vec<MazeNode> {
 fn visit_next(
     coordinate: usize,
     width: usize,
-    height: usize,
+    mask: &[bool],
     maze: &mut [MazeNode],
     connections: &mut DisjointSet,
     visitable: &mut Vec<MazeDirection>,
+    choose: &mut impl FnMut(&mut Vec<MazeDirection>) -> Option<MazeDirection>,
 ) -> Option<usize> {
     while !visitable.is_empty() {
-        let rand_idx = choose_random(visitable).expect("visitable should be non-empty");
+        let rand_idx = choose(visitable).expect("visitable should be non-empty");
         match rand_idx {
             MazeDirection::Right => {
                 if (coordinate % width) == (width - 1) {
@@ -55,9 +170,10 @@ fn visit_next(
 
                 let next = coordinate + 1;
 
-                if connections
-                    .common_set(coordinate, next)
-                    .expect("coordinate and next should be present in the set")
+                if !mask[next]
+                    || connections
+                        .common_set(coordinate, next)
+                        .expect("coordinate and next should be present in the set")
                 {
                     continue;
                 }
@@ -69,15 +185,16 @@ fn visit_next(
                 return Some(next);
             }
             MazeDirection::Down => {
-                if coordinate >= width * (height - 1) {
+                if coordinate + width >= maze.len() {
                     continue;
                 }
 
                 let next = coordinate + width;
 
-                if connections
-                    .common_set(coordinate, next)
-                    .expect("coordinate and next should be present in the set")
+                if !mask[next]
+                    || connections
+                        .common_set(coordinate, next)
+                        .expect("coordinate and next should be present in the set")
                 {
                     continue;
                 }
@@ -95,9 +212,10 @@ fn visit_next(
 
                 let next = coordinate - 1;
 
-                if connections
-                    .common_set(coordinate, next)
-                    .expect("coordinate and next should be present in the set")
+                if !mask[next]
+                    || connections
+                        .common_set(coordinate, next)
+                        .expect("coordinate and next should be present in the set")
                 {
                     continue;
                 }
@@ -115,9 +233,10 @@ fn visit_next(
 
                 let next = coordinate - width;
 
-                if connections
-                    .common_set(coordinate, next)
-                    .expect("coordinate and next should be present in the set")
+                if !mask[next]
+                    || connections
+                        .common_set(coordinate, next)
+                        .expect("coordinate and next should be present in the set")
                 {
                     continue;
                 }
@@ -0,0 +1,335 @@
+use image::RgbImage;
+use rand::thread_rng;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    util::{choose_random, BLACK_PIXEL, WHITE_PIXEL},
+    RgbBuffer,
+};
+
+use super::{matches_dimensions, pixel_length, MazeDirection, MazeError};
+
+const CELL_SIZE: u32 = 20;
+const INSET: u32 = 5;
+
+/// Which of the two passages through a crossing cell is drawn on top
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Crossing {
+    HorizontalOver,
+    VerticalOver,
+}
+
+/// A maze cell whose horizontal and vertical passages can cross without connecting
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WeaveCell {
+    right: bool,
+    down: bool,
+    crossing: Option<Crossing>,
+}
+
+impl WeaveCell {
+    fn new() -> Self {
+        WeaveCell {
+            right: true,
+            down: true,
+            crossing: None,
+        }
+    }
+
+    pub fn crossing(&self) -> Option<Crossing> {
+        self.crossing
+    }
+}
+
+/// A maze where corridors may pass over or under each other, entered above the first cell and exited below the last
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WeaveMaze {
+    pub width: usize,
+    pub height: usize,
+    pub cells: Vec<WeaveCell>,
+}
+
+impl WeaveMaze {
+    fn is_open(&self, cell: usize, direction: &MazeDirection) -> bool {
+        match direction {
+            MazeDirection::Right => !self.cells[cell].right,
+            MazeDirection::Down => !self.cells[cell].down,
+            MazeDirection::Left => cell % self.width != 0 && !self.cells[cell - 1].right,
+            MazeDirection::Up => cell >= self.width && !self.cells[cell - self.width].down,
+        }
+    }
+
+    fn open(&mut self, cell: usize, direction: &MazeDirection) {
+        match direction {
+            MazeDirection::Right => self.cells[cell].right = false,
+            MazeDirection::Down => self.cells[cell].down = false,
+            MazeDirection::Left => self.cells[cell - 1].right = false,
+            MazeDirection::Up => self.cells[cell - self.width].down = false,
+        }
+    }
+
+    fn step(&self, cell: usize, direction: &MazeDirection) -> Option<usize> {
+        match direction {
+            MazeDirection::Right => (cell % self.width + 1 < self.width).then_some(cell + 1),
+            MazeDirection::Down => {
+                (cell / self.width + 1 < self.height).then_some(cell + self.width)
+            }
+            MazeDirection::Left => (cell % self.width != 0).then(|| cell - 1),
+            MazeDirection::Up => cell.checked_sub(self.width),
+        }
+    }
+
+    /// A visited cell can be tunneled under if it's a straight corridor running across 'direction'
+    fn can_tunnel(&self, cell: usize, direction: &MazeDirection) -> bool {
+        let (along, across) = match direction {
+            MazeDirection::Right | MazeDirection::Left => (
+                [MazeDirection::Right, MazeDirection::Left],
+                [MazeDirection::Down, MazeDirection::Up],
+            ),
+            MazeDirection::Down | MazeDirection::Up => (
+                [MazeDirection::Down, MazeDirection::Up],
+                [MazeDirection::Right, MazeDirection::Left],
+            ),
+        };
+
+        self.cells[cell].crossing.is_none()
+            && along.iter().all(|direction| !self.is_open(cell, direction))
+            && across.iter().all(|direction| self.is_open(cell, direction))
+    }
+}
+
+/// Generates a weave maze with a recursive backtracker that may tunnel under straight corridors
+pub fn create_weave_maze(width: usize, height: usize) -> Result<WeaveMaze, MazeError> {
+    if width == 0 || height == 0 || width.checked_mul(height).is_none() {
+        return Err(MazeError::InvalidDimensions);
+    }
+
+    let mut maze = WeaveMaze {
+        width,
+        height,
+        cells: vec![WeaveCell::new(); width * height],
+    };
+
+    let mut visited = vec![false; width * height];
+    visited[0] = true;
+
+    let mut can_visit = vec![
+        vec![
+            MazeDirection::Right,
+            MazeDirection::Down,
+            MazeDirection::Left,
+            MazeDirection::Up
+        ];
+        width * height
+    ];
+
+    let mut path = vec![0];
+    let mut rng = thread_rng();
+
+    while let Some(&cell) = path.last() {
+        let Some(direction) = choose_random(&mut can_visit[cell], &mut rng) else {
+            path.pop();
+            continue;
+        };
+
+        let Some(next) = maze.step(cell, &direction) else {
+            continue;
+        };
+
+        if !visited[next] {
+            maze.open(cell, &direction);
+            visited[next] = true;
+            path.push(next);
+            continue;
+        }
+
+        let Some(beyond) = maze.step(next, &direction) else {
+            continue;
+        };
+
+        if visited[beyond] || !maze.can_tunnel(next, &direction) {
+            continue;
+        }
+
+        maze.open(cell, &direction);
+        maze.open(next, &direction);
+        maze.cells[next].crossing = Some(match direction {
+            MazeDirection::Right | MazeDirection::Left => Crossing::VerticalOver,
+            MazeDirection::Down | MazeDirection::Up => Crossing::HorizontalOver,
+        });
+        visited[beyond] = true;
+        path.push(beyond);
+    }
+
+    maze.cells[width * height - 1].down = false;
+
+    Ok(maze)
+}
+
+/// Draws corridors inset within 20px cells, leaving a gap where the lower passage runs under a crossing
+pub fn print_weave_maze(maze: &WeaveMaze) -> Result<RgbBuffer, MazeError> {
+    if !matches_dimensions(maze.width, maze.height, maze.cells.len()) || maze.cells.is_empty() {
+        return Err(MazeError::InvalidDimensions);
+    }
+
+    let mut image = RgbImage::from_pixel(
+        pixel_length(maze.width, CELL_SIZE)?,
+        pixel_length(maze.height, CELL_SIZE)?,
+        WHITE_PIXEL,
+    );
+
+    let near = INSET;
+    let far = CELL_SIZE - INSET - 1;
+    let edge = CELL_SIZE - 1;
+
+    for (i, cell) in maze.cells.iter().enumerate() {
+        let x = (i % maze.width) as u32 * CELL_SIZE;
+        let y = (i / maze.width) as u32 * CELL_SIZE;
+
+        match cell.crossing {
+            Some(Crossing::HorizontalOver) => {
+                horizontal(&mut image, x, y, 0, edge, near);
+                horizontal(&mut image, x, y, 0, edge, far);
+                vertical(&mut image, x, y, 0, near - 2, near);
+                vertical(&mut image, x, y, far + 2, edge, near);
+                vertical(&mut image, x, y, 0, near - 2, far);
+                vertical(&mut image, x, y, far + 2, edge, far);
+            }
+            Some(Crossing::VerticalOver) => {
+                vertical(&mut image, x, y, 0, edge, near);
+                vertical(&mut image, x, y, 0, edge, far);
+                horizontal(&mut image, x, y, 0, near - 2, near);
+                horizontal(&mut image, x, y, far + 2, edge, near);
+                horizontal(&mut image, x, y, 0, near - 2, far);
+                horizontal(&mut image, x, y, far + 2, edge, far);
+            }
+            None => {
+                if i == 0 || maze.is_open(i, &MazeDirection::Up) {
+                    vertical(&mut image, x, y, 0, near, near);
+                    vertical(&mut image, x, y, 0, near, far);
+                } else {
+                    horizontal(&mut image, x, y, near, far, near);
+                }
+
+                if maze.is_open(i, &MazeDirection::Down) {
+                    vertical(&mut image, x, y, far, edge, near);
+                    vertical(&mut image, x, y, far, edge, far);
+                } else {
+                    horizontal(&mut image, x, y, near, far, far);
+                }
+
+                if maze.is_open(i, &MazeDirection::Left) {
+                    horizontal(&mut image, x, y, 0, near, near);
+                    horizontal(&mut image, x, y, 0, near, far);
+                } else {
+                    vertical(&mut image, x, y, near, far, near);
+                }
+
+                if maze.is_open(i, &MazeDirection::Right) {
+                    horizontal(&mut image, x, y, far, edge, near);
+                    horizontal(&mut image, x, y, far, edge, far);
+                } else {
+                    vertical(&mut image, x, y, near, far, far);
+                }
+            }
+        }
+    }
+
+    Ok(image)
+}
+
+fn horizontal(image: &mut RgbImage, x: u32, y: u32, from: u32, to: u32, row: u32) {
+    for k in from..=to {
+        image.put_pixel(x + k, y + row, BLACK_PIXEL);
+    }
+}
+
+fn vertical(image: &mut RgbImage, x: u32, y: u32, from: u32, to: u32, col: u32) {
+    for k in from..=to {
+        image.put_pixel(x + col, y + k, BLACK_PIXEL);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::util::{BLACK_PIXEL, WHITE_PIXEL};
+
+    use super::{Crossing, WeaveCell, WeaveMaze};
+
+    #[test]
+    fn create_weave_maze() {
+        let maze = super::create_weave_maze(12, 10).expect("should be ok");
+
+        let crossings = maze
+            .cells
+            .iter()
+            .filter(|cell| cell.crossing.is_some())
+            .count();
+
+        let passages = maze
+            .cells
+            .iter()
+            .map(|cell| usize::from(!cell.right) + usize::from(!cell.down))
+            .sum::<usize>()
+            - 1;
+
+        assert_eq!(passages, 12 * 10 - 1 + crossings);
+
+        for (i, cell) in maze.cells.iter().enumerate() {
+            if cell.crossing.is_some() {
+                assert!(!cell.right && !cell.down);
+                assert!(!maze.cells[i - 1].right && !maze.cells[i - 12].down);
+            }
+        }
+    }
+
+    #[test]
+    fn create_weave_maze_empty() {
+        assert!(super::create_weave_maze(0, 4).is_err());
+        assert!(super::create_weave_maze(usize::MAX, 2).is_err());
+    }
+
+    #[test]
+    fn print_crossing() {
+        let open = |right, down| WeaveCell {
+            right,
+            down,
+            crossing: None,
+        };
+
+        let mut cells = vec![
+            open(true, true),
+            open(true, false),
+            open(true, true),
+            open(false, true),
+            open(false, false),
+            open(true, true),
+            open(true, true),
+            open(true, true),
+            open(true, true),
+        ];
+        cells[4].crossing = Some(Crossing::HorizontalOver);
+
+        let maze = WeaveMaze {
+            width: 3,
+            height: 3,
+            cells,
+        };
+
+        let image = super::print_weave_maze(&maze).expect("should be ok");
+        assert_eq!(image.dimensions(), (60, 60));
+
+        // the horizontal corridor's walls run through the crossing
+        assert_eq!(*image.get_pixel(20, 25), BLACK_PIXEL);
+        assert_eq!(*image.get_pixel(39, 34), BLACK_PIXEL);
+
+        // the vertical corridor stops short of them
+        assert_eq!(*image.get_pixel(25, 23), BLACK_PIXEL);
+        assert_eq!(*image.get_pixel(25, 24), WHITE_PIXEL);
+        assert_eq!(*image.get_pixel(25, 35), WHITE_PIXEL);
+        assert_eq!(*image.get_pixel(25, 36), BLACK_PIXEL);
+
+        // the corridor itself is clear
+        assert_eq!(*image.get_pixel(30, 30), WHITE_PIXEL);
+    }
+}
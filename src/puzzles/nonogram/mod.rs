@@ -1,22 +1,31 @@
 mod right_left;
 
-use std::cmp::max;
+use std::{
+    cmp::max,
+    sync::atomic::{AtomicBool, Ordering},
+};
 
 use ab_glyph::FontRef;
-use image::ImageBuffer;
+use image::{imageops::replace, ImageBuffer};
 use imageproc::{
-    drawing::{draw_filled_rect_mut, draw_text_mut},
+    drawing::{draw_filled_rect_mut, draw_line_segment_mut, draw_text_mut, text_size},
     rect::Rect,
 };
+#[cfg(feature = "rayon")]
+use rayon::prelude::{IntoParallelRefIterator, ParallelIterator};
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 use crate::{
-    util::{BLACK_PIXEL, GRAY_PIXEL, ROBOTO_MEDIUM, WHITE_PIXEL},
+    structures::grid::Grid,
+    util::{BLACK_PIXEL, GRAY_PIXEL, LIGHT_GRAY_PIXEL, ROBOTO_MEDIUM, WHITE_PIXEL},
     RgbBuffer,
 };
 
 use self::right_left::RuleMachine;
 
+const MIN_RULE_SIZE: u32 = 150;
+
 #[derive(Debug, Error)]
 pub enum NonogramError {
     #[error("puzzle cannot be empty")]
@@ -27,32 +36,39 @@ pub enum NonogramError {
     InvalidRuleDimension,
     #[error("puzzle has no solution")]
     NoSolution,
+    #[error("puzzle has more than one solution")]
+    NotUnique,
     #[error("invalid dimensions")]
     InvalidDimensions,
+    #[error("solve was cancelled")]
+    Cancelled,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NonogramPuzzle {
+    pub col: Vec<Vec<usize>>,
+    pub row: Vec<Vec<usize>>,
+    pub solution: Option<Vec<bool>>,
 }
 
 pub fn parse_nonogram_rules(rules: &str, bound: usize) -> Result<Vec<Vec<usize>>, NonogramError> {
     rules
         .split(';')
         .map(|rule| {
-            let mut size = 0;
-
             let values = rule
                 .split(',')
                 .map(|x| {
-                    let value = x
-                        .parse::<usize>()
-                        .or(Err(NonogramError::InvalidRule(x.into())))?;
-
-                    size += value;
-
-                    Ok(value)
+                    x.parse::<usize>()
+                        .or(Err(NonogramError::InvalidRule(x.into())))
                 })
                 .collect::<Result<Vec<usize>, NonogramError>>()?;
 
-            size += values.len() - 1;
+            // a lone `0` marks an empty line, but a zero-length run inside a rule is meaningless
+            if values.len() > 1 && values.contains(&0) {
+                return Err(NonogramError::InvalidRule(rule.into()));
+            }
 
-            if values.is_empty() || size > bound {
+            if !rule_fits(&values, bound) {
                 return Err(NonogramError::InvalidRuleDimension);
             }
 
@@ -61,52 +77,312 @@ pub fn parse_nonogram_rules(rules: &str, bound: usize) -> Result<Vec<Vec<usize>>
         .collect::<Result<Vec<Vec<usize>>, NonogramError>>()
 }
 
-#[derive(Debug, Clone, PartialEq)]
-enum Square {
+/// Parses column and row rules written as for `parse_nonogram_rules` into a puzzle without a solution, bounding each
+/// axis's rules by the number of lines along the other
+pub fn parse_nonogram(col: &str, row: &str) -> Result<NonogramPuzzle, NonogramError> {
+    let col_count = col.split(';').count();
+    let row_count = row.split(';').count();
+
+    Ok(NonogramPuzzle {
+        col: parse_nonogram_rules(col, row_count)?,
+        row: parse_nonogram_rules(row, col_count)?,
+        solution: None,
+    })
+}
+
+/// Checks that a rule's runs and the gaps between them fit within 'bound' cells, without overflowing on huge runs
+fn rule_fits(rule: &[usize], bound: usize) -> bool {
+    rule.iter()
+        .try_fold(rule.len().saturating_sub(1), |size, &value| {
+            size.checked_add(value)
+        })
+        .is_some_and(|size| !rule.is_empty() && size <= bound)
+}
+
+/// Checks that every column rule fits within the number of rows and every row rule fits within the number of columns
+pub fn validate_nonogram_dimensions(
+    col: &[Vec<usize>],
+    row: &[Vec<usize>],
+) -> Result<(), NonogramError> {
+    if col.is_empty() || row.is_empty() {
+        return Err(NonogramError::EmptyPuzzle);
+    }
+
+    let fits = |rules: &[Vec<usize>], bound: usize| rules.iter().all(|rule| rule_fits(rule, bound));
+
+    if !fits(col, row.len()) || !fits(row, col.len()) {
+        return Err(NonogramError::InvalidRuleDimension);
+    }
+
+    Ok(())
+}
+
+/// The state of a single nonogram cell while solving
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NonogramCell {
+    /// Not yet known to be filled or blocked
     Blank,
     Filled,
+    /// Known to be empty
     Blocked,
 }
 
+impl NonogramCell {
+    /// `#` for filled, `.` for blank, and `X` for blocked
+    pub fn to_char(self) -> char {
+        match self {
+            NonogramCell::Blank => '.',
+            NonogramCell::Filled => '#',
+            NonogramCell::Blocked => 'X',
+        }
+    }
+}
+
+impl From<bool> for NonogramCell {
+    fn from(filled: bool) -> Self {
+        if filled {
+            NonogramCell::Filled
+        } else {
+            NonogramCell::Blocked
+        }
+    }
+}
+
+impl From<NonogramCell> for bool {
+    fn from(cell: NonogramCell) -> Self {
+        matches!(cell, NonogramCell::Filled)
+    }
+}
+
 pub fn solve_nonogram(col: &[Vec<usize>], row: &[Vec<usize>]) -> Result<Vec<bool>, NonogramError> {
+    solve_nonogram_with_progress(col, row, |_| {})
+}
+
+/// Same as `solve_nonogram`, calling 'on_progress' with the partially solved grid after every line-solving sweep
+pub fn solve_nonogram_with_progress(
+    col: &[Vec<usize>],
+    row: &[Vec<usize>],
+    on_progress: impl FnMut(&[NonogramCell]),
+) -> Result<Vec<bool>, NonogramError> {
+    solve(col, row, &AtomicBool::new(false), on_progress)
+}
+
+/// Same as `solve_nonogram`, but gives up with `Cancelled` once 'cancel' is set, which is checked before each sweep
+pub fn solve_nonogram_cancellable(
+    col: &[Vec<usize>],
+    row: &[Vec<usize>],
+    cancel: &AtomicBool,
+) -> Result<Vec<bool>, NonogramError> {
+    solve(col, row, cancel, |_| {})
+}
+
+fn solve(
+    col: &[Vec<usize>],
+    row: &[Vec<usize>],
+    cancel: &AtomicBool,
+    on_progress: impl FnMut(&[NonogramCell]),
+) -> Result<Vec<bool>, NonogramError> {
+    validate_nonogram_dimensions(col, row)?;
+
     let width = col.len();
     let height = row.len();
 
-    let mut grid = vec![Square::Blank; width * height];
+    let mut grid = Grid::new(width, height, NonogramCell::Blank);
 
-    right_left(&mut grid, col, row)?;
-    recursive_backtrack(&mut grid, col, row);
+    right_left(&mut grid, col, row, cancel, on_progress)?;
+    recursive_backtrack(&mut grid, col, row, cancel)?;
 
-    Ok(grid
+    Ok(grid.into_vec().into_iter().map(bool::from).collect())
+}
+
+/// Checks each puzzle's dimensions and that it has exactly one solution, in parallel with the `rayon` feature
+///
+/// Puzzles that contradict themselves give `NoSolution` and ambiguous ones give `NotUnique`
+pub fn validate_nonogram_collection(puzzles: &[NonogramPuzzle]) -> Vec<Result<(), NonogramError>> {
+    #[cfg(feature = "rayon")]
+    let puzzles = puzzles.par_iter();
+    #[cfg(not(feature = "rayon"))]
+    let puzzles = puzzles.iter();
+
+    puzzles
+        .map(
+            |puzzle| match count_solutions(&puzzle.col, &puzzle.row, 2)? {
+                0 => Err(NonogramError::NoSolution),
+                1 => Ok(()),
+                _ => Err(NonogramError::NotUnique),
+            },
+        )
+        .collect()
+}
+
+/// Counts solutions, stopping once 'limit' have been found
+fn count_solutions(
+    col: &[Vec<usize>],
+    row: &[Vec<usize>],
+    limit: usize,
+) -> Result<usize, NonogramError> {
+    validate_nonogram_dimensions(col, row)?;
+
+    let mut grid = Grid::new(col.len(), row.len(), NonogramCell::Blank);
+    count_from(&mut grid, col, row, limit)
+}
+
+/// Line solves 'grid', then tries both values for the first cell left undecided and counts each branch's solutions
+fn count_from(
+    grid: &mut Grid<NonogramCell>,
+    col: &[Vec<usize>],
+    row: &[Vec<usize>],
+    limit: usize,
+) -> Result<usize, NonogramError> {
+    // A line with no arrangement left means this branch contradicts the rules
+    match right_left(grid, col, row, &AtomicBool::new(false), |_| {}) {
+        Err(NonogramError::NoSolution) => return Ok(0),
+        result => result?,
+    }
+
+    let Some(blank) = grid.iter().position(|&cell| cell == NonogramCell::Blank) else {
+        let solution = grid
+            .iter()
+            .map(|&cell| bool::from(cell))
+            .collect::<Vec<_>>();
+        return Ok(usize::from(verify_nonogram(col, row, &solution)));
+    };
+
+    let mut count = 0;
+
+    for guess in [NonogramCell::Filled, NonogramCell::Blocked] {
+        if count >= limit {
+            break;
+        }
+
+        let mut branch = grid.clone();
+        branch[blank] = guess;
+        count += count_from(&mut branch, col, row, limit - count)?;
+    }
+
+    Ok(count)
+}
+
+/// Checks that the filled cells of 'grid' form exactly the runs given by the rules, which can't describe an empty
+/// puzzle
+pub fn verify_nonogram(col: &[Vec<usize>], row: &[Vec<usize>], grid: &[bool]) -> bool {
+    let width = col.len();
+
+    if col.is_empty() || row.is_empty() || width.checked_mul(row.len()) != Some(grid.len()) {
+        return false;
+    }
+
+    let matches = |rule: &[usize], line: &mut dyn Iterator<Item = bool>| {
+        let mut runs = Vec::new();
+        let mut run = 0;
+
+        for filled in line.chain([false]) {
+            if filled {
+                run += 1;
+            } else if run > 0 {
+                runs.push(run);
+                run = 0;
+            }
+        }
+
+        runs.iter().eq(rule.iter().filter(|&&value| value != 0))
+    };
+
+    col.iter()
+        .enumerate()
+        .all(|(x, rule)| matches(rule, &mut grid[x..].iter().step_by(width).copied()))
+        && row
+            .iter()
+            .zip(grid.chunks(width.max(1)))
+            .all(|(rule, line)| matches(rule, &mut line.iter().copied()))
+}
+
+/// Estimates difficulty as the fraction of cells line solving alone leaves undecided, from 0 (no guessing needed) to 1
+pub fn nonogram_difficulty(col: &[Vec<usize>], row: &[Vec<usize>]) -> Result<f32, NonogramError> {
+    validate_nonogram_dimensions(col, row)?;
+
+    let mut grid = Grid::new(col.len(), row.len(), NonogramCell::Blank);
+    right_left(&mut grid, col, row, &AtomicBool::new(false), |_| {})?;
+
+    let blank = grid
         .iter()
-        .map(|square| matches!(square, Square::Filled))
-        .collect())
+        .filter(|&&cell| cell == NonogramCell::Blank)
+        .count();
+
+    Ok(blank as f32 / grid.len() as f32)
+}
+
+/// How a player's attempt compares to the solution, counting only the cells they filled
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GradeResult {
+    /// Filled cells that are filled in the solution
+    pub correct: usize,
+    /// Filled cells that are empty in the solution
+    pub incorrect: usize,
+    /// Whether the attempt matches the solution exactly
+    pub complete: bool,
+}
+
+pub fn grade_nonogram(
+    col: &[Vec<usize>],
+    row: &[Vec<usize>],
+    attempt: &[bool],
+) -> Result<GradeResult, NonogramError> {
+    if attempt.len() != col.len() * row.len() {
+        return Err(NonogramError::InvalidDimensions);
+    }
+
+    let solution = solve_nonogram(col, row)?;
+
+    let mut correct = 0;
+    let mut incorrect = 0;
+
+    for (&filled, &expected) in attempt.iter().zip(&solution) {
+        match (filled, expected) {
+            (true, true) => correct += 1,
+            (true, false) => incorrect += 1,
+            _ => {}
+        }
+    }
+
+    Ok(GradeResult {
+        correct,
+        incorrect,
+        complete: attempt == solution,
+    })
 }
 
 fn right_left(
-    grid: &mut [Square],
+    grid: &mut Grid<NonogramCell>,
     col: &[Vec<usize>],
     row: &[Vec<usize>],
+    cancel: &AtomicBool,
+    mut on_sweep: impl FnMut(&[NonogramCell]),
 ) -> Result<(), NonogramError> {
-    let width = col.len();
-
     let col_machines: Vec<RuleMachine> = col.iter().map(|rule| RuleMachine::new(rule)).collect();
     let row_machines: Vec<RuleMachine> = row.iter().map(|rule| RuleMachine::new(rule)).collect();
 
+    // Columns are solved on a transposed copy so every line is a contiguous slice
+    let mut columns = grid.transposed();
+
     loop {
+        if cancel.load(Ordering::Relaxed) {
+            return Err(NonogramError::Cancelled);
+        }
+
         let mut changed = false;
 
         for (index, machine) in col_machines.iter().enumerate() {
-            changed |= machine.right_left(grid[index..].iter_mut().step_by(width).collect())?;
+            changed |= machine.right_left(columns.row_mut(index))?;
         }
+        grid.copy_transposed(&columns);
 
         for (index, machine) in row_machines.iter().enumerate() {
-            changed |= machine.right_left(
-                grid[width * index..width * (index + 1)]
-                    .iter_mut()
-                    .collect(),
-            )?;
+            changed |= machine.right_left(grid.row_mut(index))?;
         }
+        columns.copy_transposed(grid);
+
+        on_sweep(grid);
 
         if !changed {
             break;
@@ -116,7 +392,45 @@ fn right_left(
     Ok(())
 }
 
-fn recursive_backtrack(grid: &mut [Square], col: &[Vec<usize>], row: &[Vec<usize>]) {}
+/// Decides the cells line solving left blank by guessing the first one and line solving each guess in turn, keeping
+/// the first branch that leads to a solution
+fn recursive_backtrack(
+    grid: &mut Grid<NonogramCell>,
+    col: &[Vec<usize>],
+    row: &[Vec<usize>],
+    cancel: &AtomicBool,
+) -> Result<(), NonogramError> {
+    let Some(blank) = grid.iter().position(|&cell| cell == NonogramCell::Blank) else {
+        let solution = grid
+            .iter()
+            .map(|&cell| bool::from(cell))
+            .collect::<Vec<_>>();
+
+        if !verify_nonogram(col, row, &solution) {
+            return Err(NonogramError::NoSolution);
+        }
+
+        return Ok(());
+    };
+
+    for guess in [NonogramCell::Filled, NonogramCell::Blocked] {
+        let mut branch = grid.clone();
+        branch[blank] = guess;
+
+        match right_left(&mut branch, col, row, cancel, |_| {})
+            .and_then(|()| recursive_backtrack(&mut branch, col, row, cancel))
+        {
+            Ok(()) => {
+                *grid = branch;
+                return Ok(());
+            }
+            Err(NonogramError::NoSolution) => {}
+            Err(error) => return Err(error),
+        }
+    }
+
+    Err(NonogramError::NoSolution)
+}
 
 pub fn print_nonogram(
     width: u32,
@@ -128,8 +442,9 @@ pub fn print_nonogram(
         return Err(NonogramError::InvalidDimensions);
     }
 
-    let rule_width = max(150, width * 10);
-    let rule_height = max(150, height * 10);
+    let font = FontRef::try_from_slice(ROBOTO_MEDIUM).expect("Font should be valid");
+
+    let (rule_width, rule_height) = rule_size(col, row, &font);
 
     let mut image = ImageBuffer::from_pixel(
         width * 50 + rule_width,
@@ -137,8 +452,6 @@ pub fn print_nonogram(
         WHITE_PIXEL,
     );
 
-    let font = FontRef::try_from_slice(ROBOTO_MEDIUM).expect("Font should be valid");
-
     for (x, rule) in col.iter().enumerate() {
         let x = (x as u32) * 50 + rule_width + 15;
 
@@ -167,11 +480,7 @@ pub fn print_nonogram(
             y as i32,
             30.0,
             &font,
-            &rule
-                .iter()
-                .map(|x| x.to_string())
-                .collect::<Vec<_>>()
-                .join("  "),
+            &row_rule_text(rule),
         );
     }
 
@@ -206,12 +515,16 @@ pub fn print_nonogram_solution(
     mut image: RgbBuffer,
     grid: &[bool],
 ) -> Result<RgbBuffer, NonogramError> {
-    let rule_width = max(150, width * 10);
-    let rule_height = max(150, height * 10);
+    let rule_width = image.width().checked_sub(width * 50);
+    let rule_height = image.height().checked_sub(height * 50);
+
+    let (Some(rule_width), Some(rule_height)) = (rule_width, rule_height) else {
+        return Err(NonogramError::InvalidDimensions);
+    };
 
     if grid.len() != width as usize * height as usize
-        || image.width() != width * 50 + rule_width
-        || image.height() != height * 50 + rule_height
+        || rule_width < MIN_RULE_SIZE
+        || rule_height < MIN_RULE_SIZE
     {
         return Err(NonogramError::InvalidDimensions);
     }
@@ -221,12 +534,12 @@ pub fn print_nonogram_solution(
             continue;
         }
 
-        let x = (i as u32 % width) * 50 + max(150, width * 10) + 1;
-        let y = (i as u32 / width) * 50 + max(150, height * 10) + 1;
+        let (x, w) = separated_span(i as u32 % width, width);
+        let (y, h) = separated_span(i as u32 / width, height);
 
         draw_filled_rect_mut(
             &mut image,
-            Rect::at(x as i32, y as i32).of_size(49, 49),
+            Rect::at((x + rule_width) as i32, (y + rule_height) as i32).of_size(w, h),
             BLACK_PIXEL,
         );
     }
@@ -234,35 +547,275 @@ pub fn print_nonogram_solution(
     Ok(image)
 }
 
+/// Renders a partially solved grid on top of the rules, with filled cells black, blocked cells as a light X, and
+/// blank cells left empty
+pub fn print_nonogram_partial(
+    width: u32,
+    height: u32,
+    col: &[Vec<usize>],
+    row: &[Vec<usize>],
+    grid: &[NonogramCell],
+) -> Result<RgbBuffer, NonogramError> {
+    if grid.len() != width as usize * height as usize {
+        return Err(NonogramError::InvalidDimensions);
+    }
+
+    let image = print_nonogram(width, height, col, row)?;
+    let rule_width = image.width() - width * 50;
+    let rule_height = image.height() - height * 50;
+
+    let filled = grid
+        .iter()
+        .map(|&cell| cell == NonogramCell::Filled)
+        .collect::<Vec<_>>();
+    let mut image = print_nonogram_solution(width, height, image, &filled)?;
+
+    for (i, &cell) in grid.iter().enumerate() {
+        if cell != NonogramCell::Blocked {
+            continue;
+        }
+
+        let x = (i as u32 % width * 50 + rule_width) as f32;
+        let y = (i as u32 / width * 50 + rule_height) as f32;
+
+        draw_line_segment_mut(
+            &mut image,
+            (x + 10.0, y + 10.0),
+            (x + 40.0, y + 40.0),
+            LIGHT_GRAY_PIXEL,
+        );
+        draw_line_segment_mut(
+            &mut image,
+            (x + 40.0, y + 10.0),
+            (x + 10.0, y + 40.0),
+            LIGHT_GRAY_PIXEL,
+        );
+    }
+
+    Ok(image)
+}
+
+/// Renders the puzzle with its solution filled in, sizing the grid from the rules
+pub fn print_nonogram_solved(
+    col: &[Vec<usize>],
+    row: &[Vec<usize>],
+    grid: &[bool],
+) -> Result<RgbBuffer, NonogramError> {
+    let width = u32::try_from(col.len()).or(Err(NonogramError::InvalidDimensions))?;
+    let height = u32::try_from(row.len()).or(Err(NonogramError::InvalidDimensions))?;
+
+    let image = print_nonogram(width, height, col, row)?;
+    print_nonogram_solution(width, height, image, grid)
+}
+
+/// Lays out each puzzle, rendered unsolved by `print_nonogram`, in a grid 'columns' wide, each numbered in reading
+/// order
+///
+/// Every slot is sized to fit the largest puzzle, with puzzles placed at the top left of their slots
+pub fn nonogram_sheet(
+    puzzles: &[NonogramPuzzle],
+    columns: usize,
+) -> Result<RgbBuffer, NonogramError> {
+    const MARGIN: u32 = 50;
+    const LABEL_HEIGHT: u32 = 40;
+
+    if puzzles.is_empty() {
+        return Err(NonogramError::EmptyPuzzle);
+    }
+
+    if columns == 0 {
+        return Err(NonogramError::InvalidDimensions);
+    }
+
+    let images = puzzles
+        .iter()
+        .map(|NonogramPuzzle { col, row, .. }| {
+            let width = u32::try_from(col.len()).or(Err(NonogramError::InvalidDimensions))?;
+            let height = u32::try_from(row.len()).or(Err(NonogramError::InvalidDimensions))?;
+
+            print_nonogram(width, height, col, row)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let slot_width = images.iter().map(|image| image.width()).max().unwrap_or(0) + MARGIN;
+    let slot_height =
+        images.iter().map(|image| image.height()).max().unwrap_or(0) + LABEL_HEIGHT + MARGIN;
+
+    let columns = columns.min(images.len());
+    let rows = images.len().div_ceil(columns);
+
+    let mut sheet = ImageBuffer::from_pixel(
+        columns as u32 * slot_width + MARGIN,
+        rows as u32 * slot_height + MARGIN,
+        WHITE_PIXEL,
+    );
+
+    let font = FontRef::try_from_slice(ROBOTO_MEDIUM).expect("Font should be valid");
+
+    for (index, image) in images.iter().enumerate() {
+        let x = (index % columns) as u32 * slot_width + MARGIN;
+        let y = (index / columns) as u32 * slot_height + MARGIN;
+
+        draw_text_mut(
+            &mut sheet,
+            BLACK_PIXEL,
+            x as i32,
+            y as i32,
+            30.0,
+            &font,
+            &format!("{}.", index + 1),
+        );
+        replace(&mut sheet, image, x as i64, (y + LABEL_HEIGHT) as i64);
+    }
+
+    Ok(sheet)
+}
+
+/// Returns the width of the row rules and height of the column rules, grown past the minimum to fit the longest rule
+///
+/// Column rules stack one run per 30px, while the row rules' measured width is rounded up to a multiple of 10
+fn rule_size(col: &[Vec<usize>], row: &[Vec<usize>], font: &FontRef) -> (u32, u32) {
+    let runs = col.iter().map(Vec::len).max().unwrap_or(0) as u32;
+
+    let text_width = row
+        .iter()
+        .map(|rule| text_size(30.0, font, &row_rule_text(rule)).0)
+        .max()
+        .unwrap_or(0);
+
+    (
+        max(MIN_RULE_SIZE, (text_width + 20).div_ceil(10) * 10),
+        max(MIN_RULE_SIZE, runs * 30 + 20),
+    )
+}
+
+fn row_rule_text(rule: &[usize]) -> String {
+    rule.iter()
+        .map(|x| x.to_string())
+        .collect::<Vec<_>>()
+        .join("  ")
+}
+
+/// Returns the offset and length of a cell's fill along one axis, leaving the every-5 separators visible
+fn separated_span(index: u32, bound: u32) -> (u32, u32) {
+    let mut start = index * 50 + 1;
+    let mut end = (index + 1) * 50;
+
+    if index % 5 == 0 {
+        start += 1;
+    }
+
+    if (index + 1) % 5 == 0 && index + 1 < bound {
+        end -= 1;
+    }
+
+    (start, end - start)
+}
+
+/// Renders a nonogram as text, with the column rules stacked above the grid and the row rules to its left
+///
+/// Filled cells are drawn as `#` and empty cells as `.`, or every cell is left blank when there's no solution
+pub fn nonogram_to_ascii(
+    col: &[Vec<usize>],
+    row: &[Vec<usize>],
+    solution: Option<&[bool]>,
+) -> String {
+    let cell_width = col
+        .iter()
+        .flatten()
+        .map(|value| value.to_string().len())
+        .max()
+        .unwrap_or(1);
+
+    let row_rules: Vec<String> = row
+        .iter()
+        .map(|rule| {
+            rule.iter()
+                .map(|value| value.to_string())
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .collect();
+    let rule_width = row_rules.iter().map(String::len).max().unwrap_or(0);
+
+    let depth = col.iter().map(Vec::len).max().unwrap_or(0);
+
+    let solution =
+        solution.and_then(|solution| Grid::from_vec(col.len(), row.len(), solution.to_vec()));
+
+    let mut ascii = String::new();
+
+    for line in 0..depth {
+        ascii.push_str(&" ".repeat(rule_width));
+
+        for rule in col {
+            let value = (line + rule.len())
+                .checked_sub(depth)
+                .map(|index| rule[index].to_string())
+                .unwrap_or_default();
+
+            ascii.push_str(&format!(" {value:>cell_width$}"));
+        }
+
+        ascii.push('\n');
+    }
+
+    for (y, rule) in row_rules.iter().enumerate() {
+        ascii.push_str(&format!("{rule:>rule_width$}"));
+
+        for x in 0..col.len() {
+            let cell = match solution.as_ref().and_then(|solution| solution.get(x, y)) {
+                Some(true) => '#',
+                Some(false) => '.',
+                None => ' ',
+            };
+
+            ascii.push_str(&format!(" {cell:>cell_width$}"));
+        }
+
+        ascii.push('\n');
+    }
+
+    ascii
+}
+
 #[cfg(test)]
 mod tests {
-    use std::io::Cursor;
+    use std::sync::atomic::AtomicBool;
 
-    use image::ImageFormat;
+    use image::ImageBuffer;
 
-    use crate::RgbBuffer;
+    use crate::{
+        structures::grid::Grid,
+        test_util::assert_png_snapshot,
+        util::{BLACK_PIXEL, LIGHT_GRAY_PIXEL, WHITE_PIXEL},
+        RgbBuffer,
+    };
 
-    use super::Square;
+    use super::{GradeResult, NonogramCell, NonogramError, NonogramPuzzle};
 
     fn test_parse(string: &str, expected: Vec<Vec<usize>>, bound: usize) {
         let actual = super::parse_nonogram_rules(string, bound).expect("should be ok");
         assert_eq!(actual, expected);
     }
 
-    fn test_right_left(col: Vec<Vec<usize>>, row: Vec<Vec<usize>>, expected: Vec<Square>) {
-        let mut actual = vec![Square::Blank; col.len() * row.len()];
-        super::right_left(&mut actual, &col, &row).expect("should be ok");
-        assert_eq!(actual, expected);
+    fn test_right_left(col: Vec<Vec<usize>>, row: Vec<Vec<usize>>, expected: Vec<NonogramCell>) {
+        let mut actual = Grid::new(col.len(), row.len(), NonogramCell::Blank);
+        super::right_left(&mut actual, &col, &row, &AtomicBool::new(false), |_| {})
+            .expect("should be ok");
+        assert_eq!(actual.into_vec(), expected);
     }
 
     fn test_backtrack(
-        mut actual: Vec<Square>,
+        actual: Vec<NonogramCell>,
         col: Vec<Vec<usize>>,
         row: Vec<Vec<usize>>,
-        expected: Vec<Square>,
+        expected: Vec<NonogramCell>,
     ) {
-        super::recursive_backtrack(&mut actual, &col, &row);
-        assert_eq!(actual, expected);
+        let mut grid = Grid::from_vec(col.len(), row.len(), actual).expect("should be some");
+        super::recursive_backtrack(&mut grid, &col, &row, &AtomicBool::new(false))
+            .expect("should be ok");
+        assert_eq!(grid.into_vec(), expected);
     }
 
     fn test_solve(col: Vec<Vec<usize>>, row: Vec<Vec<usize>>, expected: Vec<bool>) {
@@ -271,14 +824,104 @@ mod tests {
     }
 
     fn test_print(col: Vec<Vec<usize>>, row: Vec<Vec<usize>>, expected: &[u8]) -> RgbBuffer {
-        let mut actual = Vec::new();
         let image = super::print_nonogram(col.len() as u32, row.len() as u32, &col, &row)
             .expect("should be ok");
+        assert_png_snapshot(&image, expected);
         image
-            .write_to(&mut Cursor::new(&mut actual), ImageFormat::Png)
-            .expect("should be ok");
-        assert_eq!(actual, expected);
-        image
+    }
+
+    #[test]
+    fn validate_collection() {
+        let puzzle = |col, row| NonogramPuzzle {
+            col,
+            row,
+            solution: None,
+        };
+        let puzzles = [
+            puzzle(two_two_col(), two_two_row()),
+            puzzle(vec![vec![3], vec![1]], vec![vec![1], vec![1]]),
+            puzzle(vec![vec![1], vec![1]], vec![vec![1], vec![1]]),
+            puzzle(Vec::new(), Vec::new()),
+            puzzle(vec![vec![2], vec![2]], vec![vec![2], vec![1]]),
+            // Unique, but line solving alone stalls after the second row
+            puzzle(
+                vec![vec![1], vec![1, 1], vec![2, 1], vec![2, 1], vec![2, 1]],
+                vec![vec![2], vec![3], vec![2], vec![1, 1], vec![3]],
+            ),
+        ];
+
+        let actual = super::validate_nonogram_collection(&puzzles);
+        assert_eq!(actual.len(), 6);
+        assert!(actual[0].is_ok());
+        assert!(matches!(
+            actual[1],
+            Err(super::NonogramError::InvalidRuleDimension)
+        ));
+        assert!(matches!(actual[2], Err(super::NonogramError::NotUnique)));
+        assert!(matches!(actual[3], Err(super::NonogramError::EmptyPuzzle)));
+        assert!(matches!(actual[4], Err(super::NonogramError::NoSolution)));
+        assert!(actual[5].is_ok());
+    }
+
+    #[test]
+    fn count_solutions() {
+        let diagonals = [vec![1], vec![1]];
+        assert_eq!(
+            super::count_solutions(&diagonals, &diagonals, 5).expect("should be ok"),
+            2
+        );
+        assert_eq!(
+            super::count_solutions(&diagonals, &diagonals, 1).expect("should be ok"),
+            1
+        );
+        assert_eq!(
+            super::count_solutions(&two_three_col(), &two_three_row(), 5).expect("should be ok"),
+            1
+        );
+    }
+
+    #[test]
+    fn sheet() {
+        let puzzle = |col, row| NonogramPuzzle {
+            col,
+            row,
+            solution: None,
+        };
+        let puzzles = [
+            puzzle(two_two_col(), two_two_row()),
+            puzzle(two_three_col(), two_three_row()),
+            puzzle(two_two_col(), two_two_row()),
+        ];
+
+        let actual = super::nonogram_sheet(&puzzles, 2).expect("should be ok");
+        assert_eq!(actual.dimensions(), (650, 830));
+
+        let expected =
+            super::print_nonogram(2, 3, &two_three_col(), &two_three_row()).expect("should be ok");
+        for (x, y, pixel) in expected.enumerate_pixels() {
+            assert_eq!(actual.get_pixel(x + 350, y + 90), pixel);
+        }
+
+        assert_eq!(*actual.get_pixel(349, 400), WHITE_PIXEL);
+        assert_eq!(*actual.get_pixel(500, 500), WHITE_PIXEL);
+    }
+
+    #[test]
+    fn sheet_invalid() {
+        let puzzles = [NonogramPuzzle {
+            col: two_two_col(),
+            row: two_two_row(),
+            solution: None,
+        }];
+
+        assert!(matches!(
+            super::nonogram_sheet(&puzzles, 0),
+            Err(NonogramError::InvalidDimensions)
+        ));
+        assert!(matches!(
+            super::nonogram_sheet(&[], 2),
+            Err(NonogramError::EmptyPuzzle)
+        ));
     }
 
     fn test_print_solution(
@@ -288,12 +931,9 @@ mod tests {
         grid: Vec<bool>,
         expected: &[u8],
     ) {
-        let mut actual = Vec::new();
-        super::print_nonogram_solution(width as u32, height as u32, image, &grid)
-            .expect("should be ok")
-            .write_to(&mut Cursor::new(&mut actual), ImageFormat::Png)
+        let actual = super::print_nonogram_solution(width as u32, height as u32, image, &grid)
             .expect("should be ok");
-        assert_eq!(actual, expected);
+        assert_png_snapshot(&actual, expected);
     }
 
     //// GENERAL TESTS
@@ -312,21 +952,21 @@ mod tests {
         vec![vec![2], vec![1]]
     }
 
-    fn two_two_right_left() -> Vec<Square> {
+    fn two_two_right_left() -> Vec<NonogramCell> {
         vec![
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Blocked,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Blocked,
         ]
     }
 
-    fn two_two_backtracked() -> Vec<Square> {
+    fn two_two_backtracked() -> Vec<NonogramCell> {
         vec![
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Blocked,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Blocked,
         ]
     }
 
@@ -378,6 +1018,151 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parse_empty_line() {
+        test_parse("1;0;2", vec![vec![1], vec![0], vec![2]], 3);
+    }
+
+    #[test]
+    fn parse_overflow() {
+        for rules in [
+            "18446744073709551615,1",
+            "18446744073709551615,18446744073709551615",
+            "99999999999999999999",
+            "",
+            ";",
+            "1,,2",
+        ] {
+            assert!(super::parse_nonogram_rules(rules, 5).is_err());
+        }
+
+        let huge = vec![vec![usize::MAX, usize::MAX]];
+        assert!(matches!(
+            super::validate_nonogram_dimensions(&huge, &[vec![1]]),
+            Err(super::NonogramError::InvalidRuleDimension)
+        ));
+    }
+
+    #[test]
+    fn parse_both_axes() {
+        let actual = super::parse_nonogram("2;1", "1;1;1").expect("should be ok");
+        assert_eq!(
+            actual,
+            NonogramPuzzle {
+                col: vec![vec![2], vec![1]],
+                row: vec![vec![1], vec![1], vec![1]],
+                solution: None,
+            }
+        );
+
+        // Each row rule is bounded by the two columns
+        let actual = super::parse_nonogram("2;1", "1;3;1");
+        assert!(matches!(
+            actual,
+            Err(super::NonogramError::InvalidRuleDimension)
+        ));
+    }
+
+    #[test]
+    fn parse_embedded_zero() {
+        let actual = super::parse_nonogram_rules("2;1,0,2", 5);
+        assert!(
+            matches!(actual, Err(super::NonogramError::InvalidRule(rule)) if &*rule == "1,0,2")
+        );
+    }
+
+    #[test]
+    fn print_two_two_solved() {
+        let actual =
+            super::print_nonogram_solved(&two_two_col(), &two_two_row(), &two_two_solved())
+                .expect("should be ok");
+        assert_png_snapshot(&actual, TWO_TWO_SOLVED_IMAGE);
+    }
+
+    #[test]
+    fn print_many_runs() {
+        let col = vec![vec![1; 10]];
+        let row = (0..19)
+            .map(|y| if y % 2 == 0 { vec![1] } else { vec![0] })
+            .collect::<Vec<_>>();
+        let grid = (0..19).map(|y| y % 2 == 0).collect::<Vec<_>>();
+
+        let actual = super::print_nonogram_solved(&col, &row, &grid).expect("should be ok");
+        assert_eq!(actual.dimensions(), (50 + 150, 19 * 50 + 10 * 30 + 20));
+        assert_eq!(*actual.get_pixel(175, 320 + 25), BLACK_PIXEL);
+        assert_eq!(*actual.get_pixel(175, 320 + 75), WHITE_PIXEL);
+    }
+
+    #[test]
+    fn print_partial_two_two() {
+        let grid = [
+            NonogramCell::Filled,
+            NonogramCell::Blocked,
+            NonogramCell::Blank,
+            NonogramCell::Filled,
+        ];
+
+        let actual = super::print_nonogram_partial(2, 2, &two_two_col(), &two_two_row(), &grid)
+            .expect("should be ok");
+        assert_eq!(actual.dimensions(), (250, 250));
+
+        assert_eq!(*actual.get_pixel(175, 175), BLACK_PIXEL);
+        assert_eq!(*actual.get_pixel(225, 175), LIGHT_GRAY_PIXEL);
+        assert_eq!(*actual.get_pixel(225, 165), WHITE_PIXEL);
+        assert_eq!(*actual.get_pixel(175, 225), WHITE_PIXEL);
+        assert_eq!(*actual.get_pixel(225, 225), BLACK_PIXEL);
+
+        let actual =
+            super::print_nonogram_partial(2, 2, &two_two_col(), &two_two_row(), &grid[1..]);
+        assert!(matches!(
+            actual,
+            Err(super::NonogramError::InvalidDimensions)
+        ));
+    }
+
+    #[test]
+    fn print_solution_too_small() {
+        let image = ImageBuffer::from_pixel(2 * 50 + 149, 2 * 50 + 150, WHITE_PIXEL);
+        let actual = super::print_nonogram_solution(2, 2, image, &two_two_solved());
+        assert!(matches!(
+            actual,
+            Err(super::NonogramError::InvalidDimensions)
+        ));
+    }
+
+    #[test]
+    fn validate_dimensions_two_two() {
+        super::validate_nonogram_dimensions(&two_two_col(), &two_two_row()).expect("should be ok");
+    }
+
+    #[test]
+    fn validate_dimensions_swapped() {
+        let actual = super::validate_nonogram_dimensions(&two_three_col(), &two_three_col());
+        assert!(matches!(
+            actual,
+            Err(super::NonogramError::InvalidRuleDimension)
+        ));
+    }
+
+    #[test]
+    fn cell_conversions() {
+        assert_eq!(NonogramCell::from(true), NonogramCell::Filled);
+        assert_eq!(NonogramCell::from(false), NonogramCell::Blocked);
+        assert!(bool::from(NonogramCell::Filled));
+        assert!(!bool::from(NonogramCell::Blank));
+        assert!(!bool::from(NonogramCell::Blocked));
+
+        let chars: String = [
+            NonogramCell::Filled,
+            NonogramCell::Blank,
+            NonogramCell::Blocked,
+        ]
+        .into_iter()
+        .map(NonogramCell::to_char)
+        .collect();
+        assert_eq!(chars, "#.X");
+    }
+
     // two x three
     const TWO_THREE_WIDTH: usize = 2;
     const TWO_THREE_HEIGHT: usize = 3;
@@ -392,25 +1177,25 @@ mod tests {
         vec![vec![1], vec![1], vec![2]]
     }
 
-    fn two_three_right_left() -> Vec<Square> {
+    fn two_three_right_left() -> Vec<NonogramCell> {
         vec![
-            Square::Filled,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
         ]
     }
 
-    fn two_three_backtracked() -> Vec<Square> {
+    fn two_three_backtracked() -> Vec<NonogramCell> {
         vec![
-            Square::Filled,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
         ]
     }
 
@@ -462,6 +1247,106 @@ mod tests {
         );
     }
 
+    // three x twelve
+    const THREE_TWELVE_WIDTH: usize = 3;
+    const THREE_TWELVE_HEIGHT: usize = 12;
+
+    const THREE_TWELVE_COL_STRING: &str = "3,1,5;1,1,4,1;1,3,4";
+    fn three_twelve_col() -> Vec<Vec<usize>> {
+        vec![vec![3, 1, 5], vec![1, 1, 4, 1], vec![1, 3, 4]]
+    }
+
+    const THREE_TWELVE_ROW_STRING: &str = "3;1;3;1;3;1;1;3;1,1;1,1;3;1";
+    fn three_twelve_row() -> Vec<Vec<usize>> {
+        vec![
+            vec![3],
+            vec![1],
+            vec![3],
+            vec![1],
+            vec![3],
+            vec![1],
+            vec![1],
+            vec![3],
+            vec![1, 1],
+            vec![1, 1],
+            vec![3],
+            vec![1],
+        ]
+    }
+
+    fn three_twelve_solved() -> Vec<bool> {
+        "###\
+         #..\
+         ###\
+         ..#\
+         ###\
+         .#.\
+         .#.\
+         ###\
+         #.#\
+         #.#\
+         ###\
+         #.."
+        .chars()
+        .map(|cell| cell == '#')
+        .collect()
+    }
+
+    #[test]
+    fn parse_three_twelve() {
+        test_parse(
+            THREE_TWELVE_COL_STRING,
+            three_twelve_col(),
+            THREE_TWELVE_HEIGHT,
+        );
+        test_parse(
+            THREE_TWELVE_ROW_STRING,
+            three_twelve_row(),
+            THREE_TWELVE_WIDTH,
+        );
+    }
+
+    #[test]
+    fn solve_three_twelve() {
+        test_solve(
+            three_twelve_col(),
+            three_twelve_row(),
+            three_twelve_solved(),
+        );
+    }
+
+    #[test]
+    fn print_three_twelve() {
+        let actual = super::print_nonogram_solved(
+            &three_twelve_col(),
+            &three_twelve_row(),
+            &three_twelve_solved(),
+        )
+        .expect("should be ok");
+        assert_eq!(actual.dimensions(), (3 * 50 + 150, 12 * 50 + 150));
+
+        // the last row is `#..`
+        assert_eq!(*actual.get_pixel(150 + 25, 150 + 11 * 50 + 25), BLACK_PIXEL);
+        assert_eq!(
+            *actual.get_pixel(150 + 125, 150 + 11 * 50 + 25),
+            WHITE_PIXEL
+        );
+    }
+
+    #[test]
+    fn ascii_three_twelve() {
+        let actual = super::nonogram_to_ascii(
+            &three_twelve_col(),
+            &three_twelve_row(),
+            Some(&three_twelve_solved()),
+        );
+
+        let lines = actual.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 4 + THREE_TWELVE_HEIGHT);
+        assert_eq!(lines[4 + 8], "1 1 # . #");
+        assert_eq!(lines[4 + 11], "  1 # . .");
+    }
+
     // five x five
     const FIVE_FIVE_WIDTH: usize = 5;
     const FIVE_FIVE_HEIGHT: usize = 5;
@@ -476,63 +1361,63 @@ mod tests {
         vec![vec![1, 1], vec![1], vec![2], vec![4], vec![4]]
     }
 
-    fn five_five_right_left() -> Vec<Square> {
+    fn five_five_right_left() -> Vec<NonogramCell> {
         vec![
-            Square::Filled,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Filled,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Filled,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Filled,
-            Square::Filled,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Blocked,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Blocked,
+            NonogramCell::Filled,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Filled,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Filled,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Blocked,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Blocked,
         ]
     }
 
-    fn five_five_backtracked() -> Vec<Square> {
+    fn five_five_backtracked() -> Vec<NonogramCell> {
         vec![
-            Square::Filled,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Filled,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Filled,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Filled,
-            Square::Filled,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Blocked,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Blocked,
+            NonogramCell::Filled,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Filled,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Filled,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Blocked,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Blocked,
         ]
     }
 
@@ -587,6 +1472,213 @@ mod tests {
         );
     }
 
+    #[test]
+    fn ascii_five_five() {
+        let actual = super::nonogram_to_ascii(
+            &five_five_col(),
+            &five_five_row(),
+            Some(&five_five_solved()),
+        );
+        let expected = [
+            "    1        ",
+            "    2 3 4 2 1",
+            "1 1 # . . . #",
+            "  1 . . # . .",
+            "  2 . # # . .",
+            "  4 # # # # .",
+            "  4 # # # # .",
+        ];
+        assert_eq!(actual.lines().collect::<Vec<_>>(), expected);
+    }
+
+    #[test]
+    fn ascii_five_five_unsolved() {
+        let actual = super::nonogram_to_ascii(&five_five_col(), &five_five_row(), None);
+        assert_eq!(actual.lines().nth(2), Some("1 1          "));
+    }
+
+    #[test]
+    fn solve_five_five_with_progress() {
+        let mut sweeps = Vec::new();
+        let actual =
+            super::solve_nonogram_with_progress(&five_five_col(), &five_five_row(), |grid| {
+                sweeps.push(grid.to_vec())
+            })
+            .expect("should be ok");
+
+        assert_eq!(actual, five_five_solved());
+        assert_eq!(sweeps.last(), Some(&five_five_right_left()));
+
+        let known = |grid: &Vec<NonogramCell>| {
+            grid.iter()
+                .filter(|&&cell| cell != NonogramCell::Blank)
+                .count()
+        };
+        assert!(sweeps
+            .windows(2)
+            .all(|pair| known(&pair[0]) <= known(&pair[1])));
+    }
+
+    #[test]
+    fn solve_cancelled() {
+        let actual = super::solve_nonogram_cancellable(
+            &five_five_col(),
+            &five_five_row(),
+            &AtomicBool::new(true),
+        );
+        assert!(matches!(actual, Err(super::NonogramError::Cancelled)));
+    }
+
+    #[test]
+    fn verify_five_five() {
+        let mut grid = five_five_solved();
+        assert!(super::verify_nonogram(
+            &five_five_col(),
+            &five_five_row(),
+            &grid
+        ));
+
+        grid[0] = !grid[0];
+        assert!(!super::verify_nonogram(
+            &five_five_col(),
+            &five_five_row(),
+            &grid
+        ));
+
+        assert!(!super::verify_nonogram(
+            &five_five_col(),
+            &five_five_row(),
+            &grid[1..]
+        ));
+    }
+
+    #[test]
+    fn verify_empty_line() {
+        let col = vec![vec![1], vec![0]];
+        let row = vec![vec![0], vec![1]];
+
+        assert!(super::verify_nonogram(
+            &col,
+            &row,
+            &[false, false, true, false]
+        ));
+        assert!(!super::verify_nonogram(
+            &col,
+            &row,
+            &[false, false, false, false]
+        ));
+    }
+
+    #[test]
+    fn verify_empty_axis() {
+        assert!(!super::verify_nonogram(&[vec![0], vec![0]], &[], &[]));
+        assert!(!super::verify_nonogram(&[], &[vec![1]], &[]));
+    }
+
+    #[test]
+    fn difficulty() {
+        let actual = super::nonogram_difficulty(&five_five_col(), &five_five_row());
+        assert_eq!(actual.expect("should be ok"), 0.0);
+
+        let actual = super::nonogram_difficulty(&[vec![1], vec![1]], &[vec![1], vec![1]]);
+        assert_eq!(actual.expect("should be ok"), 1.0);
+
+        let actual = super::nonogram_difficulty(&[vec![1], vec![0]], &[vec![1], vec![0]]);
+        assert_eq!(actual.expect("should be ok"), 0.0);
+
+        let actual = super::nonogram_difficulty(&[vec![3]], &[vec![1]]);
+        assert!(matches!(
+            actual,
+            Err(super::NonogramError::InvalidRuleDimension)
+        ));
+    }
+
+    #[test]
+    fn solve_empty_line() {
+        test_solve(
+            vec![vec![2], vec![0], vec![1]],
+            vec![vec![1, 1], vec![1]],
+            vec![true, false, true, true, false, false],
+        );
+    }
+
+    #[test]
+    fn solve_empty() {
+        let actual = super::solve_nonogram(&[], &[]);
+        assert!(matches!(actual, Err(super::NonogramError::EmptyPuzzle)));
+
+        let actual = super::solve_nonogram(&[vec![1]], &[]);
+        assert!(matches!(actual, Err(super::NonogramError::EmptyPuzzle)));
+    }
+
+    #[test]
+    fn solve_undecided() {
+        // Two diagonals satisfy these rules, so line solving alone decides nothing and the first guess wins
+        let actual = super::solve_nonogram(&[vec![1], vec![1]], &[vec![1], vec![1]]);
+        assert_eq!(
+            actual.expect("should be ok"),
+            vec![true, false, false, true]
+        );
+    }
+
+    #[test]
+    fn solve_needs_backtracking() {
+        let col = [vec![1], vec![1, 1], vec![2, 1], vec![2, 1], vec![2, 1]];
+        let row = [vec![2], vec![3], vec![2], vec![1, 1], vec![3]];
+
+        let actual = super::solve_nonogram(&col, &row).expect("should be ok");
+        assert_eq!(
+            actual,
+            [
+                [false, false, false, true, true],
+                [false, false, true, true, true],
+                [false, true, true, false, false],
+                [true, false, false, false, true],
+                [false, true, true, true, false],
+            ]
+            .concat()
+        );
+    }
+
+    #[test]
+    fn grade_five_five() {
+        let mut attempt = five_five_solved();
+
+        let actual = super::grade_nonogram(&five_five_col(), &five_five_row(), &attempt)
+            .expect("should be ok");
+        assert_eq!(
+            actual,
+            GradeResult {
+                correct: 13,
+                incorrect: 0,
+                complete: true
+            }
+        );
+
+        attempt[0] = false;
+        attempt[1] = true;
+
+        let actual = super::grade_nonogram(&five_five_col(), &five_five_row(), &attempt)
+            .expect("should be ok");
+        assert_eq!(
+            actual,
+            GradeResult {
+                correct: 12,
+                incorrect: 1,
+                complete: false
+            }
+        );
+    }
+
+    #[test]
+    fn grade_wrong_size() {
+        let actual = super::grade_nonogram(&five_five_col(), &five_five_row(), &[true; 24]);
+        assert!(matches!(
+            actual,
+            Err(super::NonogramError::InvalidDimensions)
+        ));
+    }
+
     // Large
     const LARGE_WIDTH: usize = 25;
     const LARGE_HEIGHT: usize = 25;
@@ -653,644 +1745,644 @@ mod tests {
         ]
     }
 
-    fn large_right_left() -> Vec<Square> {
+    fn large_right_left() -> Vec<NonogramCell> {
         vec![
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Filled,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Filled,
-            Square::Blocked,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Blocked,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Blocked,
-            Square::Filled,
-            Square::Filled,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Filled,
-            Square::Filled,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Filled,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Filled,
-            Square::Filled,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Filled,
-            Square::Filled,
-            Square::Blocked,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Blocked,
-            Square::Filled,
-            Square::Blocked,
-            Square::Filled,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Filled,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Filled,
-            Square::Filled,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Blocked,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Blocked,
-            Square::Filled,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Blocked,
-            Square::Filled,
-            Square::Blocked,
-            Square::Filled,
-            Square::Blocked,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Filled,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Filled,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Filled,
-            Square::Filled,
-            Square::Blocked,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Blocked,
-            Square::Filled,
-            Square::Blocked,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Filled,
-            Square::Filled,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Blocked,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Filled,
-            Square::Filled,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Filled,
-            Square::Filled,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Filled,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Filled,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Filled,
-            Square::Blocked,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Filled,
-            Square::Filled,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Filled,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Filled,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Filled,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Filled,
-            Square::Blocked,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Blocked,
-            Square::Filled,
-            Square::Filled,
-            Square::Filled,
-            Square::Blocked,
-            Square::Blocked,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Filled,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Filled,
+            NonogramCell::Blocked,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Blocked,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Blocked,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Filled,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Blocked,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Blocked,
+            NonogramCell::Filled,
+            NonogramCell::Blocked,
+            NonogramCell::Filled,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Filled,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Blocked,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Blocked,
+            NonogramCell::Filled,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Blocked,
+            NonogramCell::Filled,
+            NonogramCell::Blocked,
+            NonogramCell::Filled,
+            NonogramCell::Blocked,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Filled,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Filled,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Blocked,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Blocked,
+            NonogramCell::Filled,
+            NonogramCell::Blocked,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Blocked,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Filled,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Filled,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Filled,
+            NonogramCell::Blocked,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Filled,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Filled,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Filled,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Filled,
+            NonogramCell::Blocked,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Filled,
+            NonogramCell::Blocked,
+            NonogramCell::Blocked,
         ]
     }
 
-    fn large_backtracked() -> Vec<Square> {
+    fn large_backtracked() -> Vec<NonogramCell> {
         large_right_left()
     }
 
     fn large_solved() -> Vec<bool> {
         large_right_left()
             .iter()
-            .map(|square| matches!(square, Square::Filled))
+            .map(|square| matches!(square, NonogramCell::Filled))
             .collect()
     }
 
@@ -1,6 +1,6 @@
 use std::mem::{swap, take};
 
-use super::{NonogramError, Square};
+use super::{NonogramCell, NonogramError};
 
 #[derive(Clone)]
 enum Node {
@@ -19,7 +19,8 @@ impl RuleMachine {
     pub fn new(rule: &[usize]) -> Self {
         let mut left_states = vec![Node::Start, Node::End];
 
-        for &value in rule {
+        // a lone `0` marks an empty line, which has no runs
+        for &value in rule.iter().filter(|&&value| value != 0) {
             left_states.extend(vec![Node::Fill; value].into_iter());
             left_states.push(Node::Space);
         }
@@ -28,7 +29,7 @@ impl RuleMachine {
 
         let mut right_states = vec![Node::Start, Node::End];
 
-        for &value in rule.iter().rev() {
+        for &value in rule.iter().rev().filter(|&&value| value != 0) {
             right_states.extend(vec![Node::Fill; value].into_iter());
             right_states.push(Node::Space);
         }
@@ -41,7 +42,7 @@ impl RuleMachine {
         }
     }
 
-    pub fn right_left(&self, mut grid: Vec<&mut Square>) -> Result<bool, NonogramError> {
+    pub fn right_left(&self, grid: &mut [NonogramCell]) -> Result<bool, NonogramError> {
         let left = find_left(&self.left_states, grid.iter()).ok_or(NonogramError::NoSolution)?;
         let right =
             find_left(&self.right_states, grid.iter().rev()).ok_or(NonogramError::NoSolution)?;
@@ -68,16 +69,16 @@ impl RuleMachine {
                 right_state = right;
             }
 
-            if !matches!(grid[i], Square::Blank) {
+            if !matches!(grid[i], NonogramCell::Blank) {
                 continue;
             }
 
             if left_block == right_block && left == right {
                 changed = true;
-                *grid[i] = if left {
-                    Square::Filled
+                grid[i] = if left {
+                    NonogramCell::Filled
                 } else {
-                    Square::Blocked
+                    NonogramCell::Blocked
                 };
             }
         }
@@ -88,7 +89,7 @@ impl RuleMachine {
 
 fn find_left<'a>(
     states: &[Node],
-    mut grid: impl Iterator<Item = &'a &'a mut Square> + Clone,
+    mut grid: impl Iterator<Item = &'a NonogramCell> + Clone,
 ) -> Option<Vec<bool>> {
     let mut old_state = vec![None; states.len() + 1];
     let mut new_state = vec![None; states.len() + 1];
@@ -109,7 +110,7 @@ fn find_left<'a>(
             match curr_state {
                 Node::Start | Node::Fill => (),
                 Node::Space | Node::End => {
-                    if !matches!(square, Some(Square::Filled)) {
+                    if !matches!(square, Some(NonogramCell::Filled)) {
                         let mut old_matches = old_matches.clone();
                         old_matches.push(state);
                         new_state[state] = Some(old_matches);
@@ -120,25 +121,25 @@ fn find_left<'a>(
             match next_state {
                 Node::Start => unreachable!(),
                 Node::Fill => {
-                    if !matches!(square, Some(Square::Blocked)) {
+                    if !matches!(square, Some(NonogramCell::Blocked)) {
                         old_matches.push(state + 1);
                         new_state[state + 1] = Some(old_matches);
                     }
                 }
                 Node::Space => {
-                    if !matches!(square, Some(Square::Filled)) {
+                    if !matches!(square, Some(NonogramCell::Filled)) {
                         old_matches.push(state + 1);
                         new_state[state + 1] = Some(old_matches);
                     }
                 }
                 Node::End => match states.get(state + 2) {
                     Some(Node::Fill) => {
-                        if !matches!(square, Some(Square::Filled)) {
+                        if !matches!(square, Some(NonogramCell::Filled)) {
                             let mut old_matches = old_matches.clone();
                             old_matches.push(state + 1);
                             new_state[state + 1] = Some(old_matches);
                         }
-                        if !matches!(square, Some(Square::Blocked)) {
+                        if !matches!(square, Some(NonogramCell::Blocked)) {
                             old_matches.push(state + 2);
                             new_state[state + 2] = Some(old_matches);
                         }
@@ -148,7 +149,7 @@ fn find_left<'a>(
                         if let Some(mut square) = square {
                             let mut grid = grid.clone();
                             loop {
-                                if matches!(square, Square::Filled) {
+                                if matches!(square, NonogramCell::Filled) {
                                     continue 'matches;
                                 }
 
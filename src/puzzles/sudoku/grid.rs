@@ -0,0 +1,124 @@
+use std::{
+    fmt::{self, Display, Formatter},
+    ops::Deref,
+};
+
+use super::{parse_sudoku, solve_sudoku, validate_sudoku, SudokuError, GRID_SIZE};
+
+/// A 9 x 9 sudoku in row-major order, with `0` for an empty space
+///
+/// Dereferences to its 81 cells, so it can be passed anywhere a `&[u8]` puzzle is expected
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SudokuGrid([u8; GRID_SIZE * GRID_SIZE]);
+
+impl SudokuGrid {
+    /// Parses 81 digits like `parse_sudoku`, rejecting givens that contradict each other
+    pub fn parse(puzzle: &str) -> Result<Self, SudokuError> {
+        let puzzle = parse_sudoku(puzzle)?;
+        validate_sudoku(&puzzle)?;
+
+        Ok(SudokuGrid(
+            puzzle.try_into().expect("puzzle should have 81 cells"),
+        ))
+    }
+
+    /// Returns the value at 'row' and 'col', or `None` if either is outside the grid
+    pub fn get(&self, row: usize, col: usize) -> Option<u8> {
+        (row < GRID_SIZE && col < GRID_SIZE).then(|| self.0[row * GRID_SIZE + col])
+    }
+
+    /// Sets the value at 'row' and 'col', where `0` clears the cell
+    pub fn set(&mut self, row: usize, col: usize, value: u8) -> Result<(), SudokuError> {
+        if row >= GRID_SIZE || col >= GRID_SIZE {
+            return Err(SudokuError::InvalidPosition { row, col });
+        }
+
+        if value > 9 {
+            return Err(SudokuError::InvalidValue(value));
+        }
+
+        self.0[row * GRID_SIZE + col] = value;
+
+        Ok(())
+    }
+
+    /// Solves the puzzle like `solve_sudoku`, returning the completed grid
+    pub fn solve(&self) -> Result<SudokuGrid, SudokuError> {
+        Ok(SudokuGrid(
+            solve_sudoku(self)?
+                .try_into()
+                .expect("solution should have 81 cells"),
+        ))
+    }
+}
+
+impl Deref for SudokuGrid {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl Display for SudokuGrid {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.0.iter().try_for_each(|digit| write!(f, "{digit}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::puzzles::sudoku::SudokuError;
+
+    use super::SudokuGrid;
+
+    const EASY_STRING: &str =
+        "415830090003009104002150006900783000200000381500012400004900063380500040009307500";
+
+    #[test]
+    fn parse() {
+        let grid = SudokuGrid::parse(EASY_STRING).expect("should be ok");
+        assert_eq!(grid.get(0, 0), Some(4));
+        assert_eq!(grid.get(1, 8), Some(4));
+        assert_eq!(grid.get(8, 0), Some(0));
+        assert_eq!(grid.get(9, 0), None);
+        assert_eq!(grid.to_string(), EASY_STRING);
+    }
+
+    #[test]
+    fn parse_invalid() {
+        assert!(matches!(
+            SudokuGrid::parse(&EASY_STRING[1..]),
+            Err(SudokuError::InvalidSize(80))
+        ));
+        assert!(matches!(
+            SudokuGrid::parse(&EASY_STRING.replacen('0', "4", 1)),
+            Err(SudokuError::DuplicateGiven(_))
+        ));
+    }
+
+    #[test]
+    fn set() {
+        let mut grid = SudokuGrid::parse(EASY_STRING).expect("should be ok");
+        grid.set(8, 0, 7).expect("should be ok");
+        assert_eq!(grid.get(8, 0), Some(7));
+
+        assert!(matches!(
+            grid.set(0, 9, 1),
+            Err(SudokuError::InvalidPosition { row: 0, col: 9 })
+        ));
+        assert!(matches!(
+            grid.set(0, 0, 10),
+            Err(SudokuError::InvalidValue(10))
+        ));
+    }
+
+    #[test]
+    fn solve() {
+        let grid = SudokuGrid::parse(EASY_STRING).expect("should be ok");
+        let solution = grid.solve().expect("should be ok");
+
+        assert!(!solution.contains(&0));
+        assert!(super::super::check_sudoku_solution(&grid, &solution).expect("should be ok"));
+    }
+}
@@ -0,0 +1,148 @@
+use super::GRID_SIZE;
+
+const ALL_CANDIDATES: u16 = 0b1_1111_1111;
+
+const UNITS: [[usize; 9]; 27] = const {
+    let mut units = [[0; 9]; 27];
+
+    let mut index = 0;
+
+    while index < 9 {
+        let mut inner_index = 0;
+        while inner_index < 9 {
+            units[index][inner_index] = index * 9 + inner_index;
+            units[index + 9][inner_index] = index + inner_index * 9;
+            units[index + 18][inner_index] =
+                (index % 3) * 3 + (index / 3) * 27 + (inner_index % 3) + (inner_index / 3) * 9;
+            inner_index += 1;
+        }
+        index += 1;
+    }
+
+    units
+};
+
+/// Bitsets of the digits that can legally be placed in each cell of a sudoku
+pub struct Mask {
+    candidates: [u16; GRID_SIZE * GRID_SIZE],
+}
+
+impl Mask {
+    /// 'puzzle' must contain exactly 81 cells
+    pub fn new(puzzle: &[u8]) -> Self {
+        let mut used = [0; 27];
+
+        for (index, &value) in puzzle.iter().enumerate() {
+            if value == 0 {
+                continue;
+            }
+
+            for unit in units_of(index) {
+                used[unit] |= 1 << (value - 1);
+            }
+        }
+
+        let mut candidates = [0; GRID_SIZE * GRID_SIZE];
+
+        for (index, &value) in puzzle.iter().enumerate() {
+            if value != 0 {
+                continue;
+            }
+
+            candidates[index] = units_of(index)
+                .iter()
+                .fold(ALL_CANDIDATES, |mask, &unit| mask & !used[unit]);
+        }
+
+        Mask { candidates }
+    }
+
+    pub fn candidates(&self, index: usize) -> Vec<u8> {
+        (1..=9)
+            .filter(|digit| self.candidates[index] & (1 << (digit - 1)) != 0)
+            .collect()
+    }
+
+    /// Returns an empty cell with no remaining candidates, if any
+    pub fn contradiction(&self, puzzle: &[u8]) -> Option<usize> {
+        (0..puzzle.len()).find(|&index| puzzle[index] == 0 && self.candidates[index] == 0)
+    }
+
+    /// Returns a cell that has exactly one candidate
+    pub fn naked_single(&self) -> Option<(usize, u8)> {
+        let index = self
+            .candidates
+            .iter()
+            .position(|candidates| candidates.count_ones() == 1)?;
+
+        Some((index, self.candidates[index].trailing_zeros() as u8 + 1))
+    }
+
+    /// Returns a cell that is the only place a digit can go within one of its units
+    pub fn hidden_single(&self) -> Option<(usize, u8)> {
+        for unit in UNITS {
+            for digit in 1..=9 {
+                let mut cells = unit
+                    .iter()
+                    .filter(|&&index| self.candidates[index] & (1 << (digit - 1)) != 0);
+
+                if let (Some(&index), None) = (cells.next(), cells.next()) {
+                    return Some((index, digit));
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Removes candidates ruled out by locked candidates, returning whether any were removed
+    ///
+    /// When a digit's candidates within a box all lie in one row or column, the digit can't appear elsewhere in that
+    /// line (pointing), and when a digit's candidates within a line all lie in one box, it can't appear elsewhere in
+    /// that box (box-line reduction)
+    pub fn eliminate_locked_candidates(&mut self) -> bool {
+        let mut eliminated = false;
+
+        for square in 18..27 {
+            for line in 0..18 {
+                eliminated |= self.eliminate_locked(square, line);
+                eliminated |= self.eliminate_locked(line, square);
+            }
+        }
+
+        eliminated
+    }
+
+    /// For each digit confined within `from` to cells that are also in `to`, clears it from the rest of `to`
+    fn eliminate_locked(&mut self, from: usize, to: usize) -> bool {
+        let mut eliminated = false;
+
+        for digit in 0..9 {
+            let bit = 1 << digit;
+            let mut cells = UNITS[from]
+                .iter()
+                .filter(|&&index| self.candidates[index] & bit != 0)
+                .peekable();
+
+            if cells.peek().is_none() || !cells.all(|index| UNITS[to].contains(index)) {
+                continue;
+            }
+
+            for &index in &UNITS[to] {
+                if !UNITS[from].contains(&index) && self.candidates[index] & bit != 0 {
+                    self.candidates[index] &= !bit;
+                    eliminated = true;
+                }
+            }
+        }
+
+        eliminated
+    }
+}
+
+pub fn units_of(index: usize) -> [usize; 3] {
+    let row = index / GRID_SIZE;
+    let col = index % GRID_SIZE;
+
+    [row, col + 9, (row / 3) * 3 + col / 3 + 18]
+}
@@ -1,16 +1,33 @@
+mod grid;
+mod mask;
+
+use std::{array::from_fn, sync::atomic::AtomicBool};
+
 use ab_glyph::FontRef;
-use image::RgbImage;
-use imageproc::drawing::draw_text_mut;
+use image::{Rgb, RgbImage};
+use imageproc::{
+    drawing::{draw_filled_rect_mut, draw_text_mut},
+    rect::Rect,
+};
+#[cfg(feature = "rayon")]
+use rayon::prelude::{IntoParallelRefIterator, ParallelIterator};
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 use crate::{
     structures::dancing_links::{DancingLinksError, DancingMatrix},
-    util::{BLACK_PIXEL, ROBOTO_MEDIUM, WHITE_PIXEL},
+    util::{BLACK_PIXEL, GREEN_PIXEL, RED_PIXEL, ROBOTO_MEDIUM, WHITE_PIXEL},
     RgbBuffer,
 };
 
+use self::mask::{units_of, Mask};
+
+pub use self::grid::SudokuGrid;
+
 const GRID_SIZE: usize = 9;
 
+const IMAGE_SIZE: u32 = GRID_SIZE as u32 * 100;
+
 const SUDOKU_CONSTRAINTS: [[usize; 9]; 324] = const {
     let mut constraints = [[0; 9]; 324];
 
@@ -37,21 +54,50 @@ const SUDOKU_CONSTRAINTS: [[usize; 9]; 324] = const {
 
 #[derive(Debug, Error)]
 pub enum SudokuError {
-    #[error("invalid integer `{0}`, must be integer 1-9 (use `0` for an empty space)")]
-    InvalidInteger(char),
+    #[error(
+        "invalid integer `{ch}` at index {index}, must be integer 1-9 (use `0` for an empty space)"
+    )]
+    InvalidInteger { ch: char, index: usize },
     #[error("sudoku must by 9 x 9, got {0} entries")]
     InvalidSize(usize),
+    #[error("invalid value `{0}`, must be 1-9 (use `0` for an empty space)")]
+    InvalidValue(u8),
+    #[error("position ({row}, {col}) is outside the grid")]
+    InvalidPosition { row: usize, col: usize },
+    #[error("given at index {0} conflicts with another given")]
+    DuplicateGiven(usize),
     #[error("sudoku has no solution")]
     NoSolution,
+    #[error("sudoku is already complete")]
+    AlreadySolved,
+    #[error("sudoku cannot be progressed without guessing")]
+    NoHint,
+    #[error("solve was cancelled")]
+    Cancelled,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Technique {
+    NakedSingle,
+    HiddenSingle,
+    /// A naked or hidden single that only appears once locked candidates (pointing pairs and box-line reduction)
+    /// have been eliminated
+    LockedCandidates,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SudokuPuzzle {
+    pub givens: Vec<u8>,
 }
 
 pub fn parse_sudoku(puzzle: &str) -> Result<Vec<u8>, SudokuError> {
     let puzzle = puzzle
         .chars()
-        .map(|char| {
-            char.to_digit(10)
+        .enumerate()
+        .map(|(index, ch)| {
+            ch.to_digit(10)
                 .map(|x| x as u8)
-                .ok_or(SudokuError::InvalidInteger(char))
+                .ok_or(SudokuError::InvalidInteger { ch, index })
         })
         .collect::<Result<Vec<u8>, SudokuError>>()?;
 
@@ -62,11 +108,75 @@ pub fn parse_sudoku(puzzle: &str) -> Result<Vec<u8>, SudokuError> {
     Ok(puzzle)
 }
 
-pub fn solve_sudoku(puzzle: &[u8]) -> Result<Vec<u8>, SudokuError> {
+/// Returns the indices of every empty space, in reading order
+pub fn sudoku_empty_cells(puzzle: &[u8]) -> Vec<usize> {
+    puzzle
+        .iter()
+        .enumerate()
+        .filter(|&(_, &value)| value == 0)
+        .map(|(index, _)| index)
+        .collect()
+}
+
+/// Parses a sudoku pasted as a grid, ignoring separators such as `|`, `-`, `+`, and line breaks
+///
+/// `.`, `_`, and spaces are treated as empty spaces alongside `0`
+pub fn parse_sudoku_grid(puzzle: &str) -> Result<Vec<u8>, SudokuError> {
+    let puzzle = puzzle
+        .chars()
+        .filter_map(|char| match char {
+            '.' | '_' | ' ' => Some(0),
+            _ => char.to_digit(10).map(|x| x as u8),
+        })
+        .collect::<Vec<u8>>();
+
+    if puzzle.len() != GRID_SIZE * GRID_SIZE {
+        return Err(SudokuError::InvalidSize(puzzle.len()));
+    }
+
+    Ok(puzzle)
+}
+
+/// Checks that a sudoku is well formed and that none of its givens contradict each other
+pub fn validate_sudoku(puzzle: &[u8]) -> Result<(), SudokuError> {
     if puzzle.len() != GRID_SIZE * GRID_SIZE {
         return Err(SudokuError::InvalidSize(puzzle.len()));
     }
 
+    let mut used = [0u16; 27];
+
+    for (index, &value) in puzzle.iter().enumerate() {
+        if value == 0 {
+            continue;
+        }
+
+        if value > 9 {
+            return Err(SudokuError::InvalidValue(value));
+        }
+
+        for unit in units_of(index) {
+            if used[unit] & (1 << (value - 1)) != 0 {
+                return Err(SudokuError::DuplicateGiven(index));
+            }
+
+            used[unit] |= 1 << (value - 1);
+        }
+    }
+
+    Ok(())
+}
+
+pub fn solve_sudoku(puzzle: &[u8]) -> Result<Vec<u8>, SudokuError> {
+    solve_sudoku_cancellable(puzzle, &AtomicBool::new(false))
+}
+
+/// Same as `solve_sudoku`, but gives up with `Cancelled` once 'cancel' is set
+pub fn solve_sudoku_cancellable(
+    puzzle: &[u8],
+    cancel: &AtomicBool,
+) -> Result<Vec<u8>, SudokuError> {
+    validate_sudoku(puzzle)?;
+
     let mut matrix = DancingMatrix::new(
         SUDOKU_CONSTRAINTS
             .iter()
@@ -85,20 +195,201 @@ pub fn solve_sudoku(puzzle: &[u8]) -> Result<Vec<u8>, SudokuError> {
         }
     }
 
-    let mut solution = matrix.solve().map_err(|_| SudokuError::NoSolution)?;
+    let mut solution = matrix.solve_cancellable(cancel).map_err(|err| match err {
+        DancingLinksError::Cancelled => SudokuError::Cancelled,
+        _ => SudokuError::NoSolution,
+    })?;
     solution.sort_unstable();
     Ok(solution.iter().map(|num| (num % 9) as u8 + 1).collect())
 }
 
+/// Solves the rest of an in-progress grid, holding every filled cell fixed rather than only the original givens
+///
+/// Filled cells that conflict with each other, or that leave no way to finish the grid, give `NoSolution`
+pub fn complete_sudoku(current: &[u8]) -> Result<Vec<u8>, SudokuError> {
+    match validate_sudoku(current) {
+        Err(SudokuError::DuplicateGiven(_)) => return Err(SudokuError::NoSolution),
+        result => result?,
+    }
+
+    solve_sudoku(current)
+}
+
+/// Checks that `candidate` is a complete, valid grid that agrees with every given in `puzzle`
+pub fn check_sudoku_solution(puzzle: &[u8], candidate: &[u8]) -> Result<bool, SudokuError> {
+    validate_sudoku(puzzle)?;
+
+    if candidate.len() != GRID_SIZE * GRID_SIZE {
+        return Err(SudokuError::InvalidSize(candidate.len()));
+    }
+
+    if let Some(&value) = candidate.iter().find(|&&value| value > 9) {
+        return Err(SudokuError::InvalidValue(value));
+    }
+
+    let mut used = [0u16; 27];
+
+    for (index, (&given, &value)) in puzzle.iter().zip(candidate).enumerate() {
+        if value == 0 || (given != 0 && given != value) {
+            return Ok(false);
+        }
+
+        for unit in units_of(index) {
+            if used[unit] & (1 << (value - 1)) != 0 {
+                return Ok(false);
+            }
+
+            used[unit] |= 1 << (value - 1);
+        }
+    }
+
+    Ok(true)
+}
+
+/// Solves a sudoku given as 81 digits (`0` for an empty space), returning the solution in the same format
+pub fn solve_sudoku_string(puzzle: &str) -> Result<String, SudokuError> {
+    let solution = solve_sudoku(&parse_sudoku(puzzle)?)?;
+
+    Ok(solution.iter().map(|digit| digit.to_string()).collect())
+}
+
+/// Solves each sudoku string in parallel
+#[cfg(feature = "rayon")]
+pub fn solve_many(puzzles: &[String]) -> Vec<Result<String, SudokuError>> {
+    puzzles
+        .par_iter()
+        .map(|puzzle| solve_sudoku_string(puzzle))
+        .collect()
+}
+
+/// Finds a single empty cell that can be filled by logic alone, returning its index, value, and the technique used
+pub fn sudoku_hint(puzzle: &[u8]) -> Result<(usize, u8, Technique), SudokuError> {
+    validate_sudoku(puzzle)?;
+
+    if !puzzle.contains(&0) {
+        return Err(SudokuError::AlreadySolved);
+    }
+
+    let mut mask = Mask::new(puzzle);
+
+    if mask.contradiction(puzzle).is_some() {
+        return Err(SudokuError::NoSolution);
+    }
+
+    if let Some((index, value)) = mask.naked_single() {
+        return Ok((index, value, Technique::NakedSingle));
+    }
+
+    if let Some((index, value)) = mask.hidden_single() {
+        return Ok((index, value, Technique::HiddenSingle));
+    }
+
+    while mask.eliminate_locked_candidates() {
+        if mask.contradiction(puzzle).is_some() {
+            return Err(SudokuError::NoSolution);
+        }
+
+        if let Some((index, value)) = mask.naked_single().or_else(|| mask.hidden_single()) {
+            return Ok((index, value, Technique::LockedCandidates));
+        }
+    }
+
+    Err(SudokuError::NoHint)
+}
+
+/// Returns the legal digits for every empty cell (filled cells have no candidates)
+pub fn sudoku_candidates(puzzle: &[u8]) -> Result<[Vec<u8>; 81], SudokuError> {
+    validate_sudoku(puzzle)?;
+
+    let mask = Mask::new(puzzle);
+
+    Ok(from_fn(|index| mask.candidates(index)))
+}
+
 pub fn print_sudoku(puzzle: &[u8]) -> Result<RgbBuffer, SudokuError> {
+    print_sudoku_highlighted(puzzle, &[], WHITE_PIXEL)
+}
+
+/// Renders the puzzle with the background of each cell in 'highlight' (given as indices) tinted 'color'
+pub fn print_sudoku_highlighted(
+    puzzle: &[u8],
+    highlight: &[usize],
+    color: Rgb<u8>,
+) -> Result<RgbBuffer, SudokuError> {
     if puzzle.len() != GRID_SIZE * GRID_SIZE {
         return Err(SudokuError::InvalidSize(puzzle.len()));
     }
 
-    const IMAGE_SIZE: u32 = GRID_SIZE as u32 * 100;
+    let mut image = RgbImage::from_pixel(IMAGE_SIZE, IMAGE_SIZE, WHITE_PIXEL);
+
+    for &index in highlight {
+        if index >= puzzle.len() {
+            return Err(SudokuError::InvalidPosition {
+                row: index / GRID_SIZE,
+                col: index % GRID_SIZE,
+            });
+        }
+
+        draw_filled_rect_mut(
+            &mut image,
+            Rect::at(
+                (index % GRID_SIZE) as i32 * 100,
+                (index / GRID_SIZE) as i32 * 100,
+            )
+            .of_size(100, 100),
+            color,
+        );
+    }
+
+    draw_grid(&mut image, puzzle, |_| BLACK_PIXEL);
+
+    Ok(image)
+}
+
+/// Renders an attempt at the puzzle, with givens in black and the attempt's digits in green where they match
+/// 'solution' and red where they don't
+///
+/// Attempted digits in cells that hold a given are ignored, and empty cells in 'attempt' are left blank
+pub fn print_sudoku_check(
+    puzzle: &[u8],
+    attempt: &[u8],
+    solution: &[u8],
+) -> Result<RgbBuffer, SudokuError> {
+    validate_sudoku(puzzle)?;
+
+    for grid in [attempt, solution] {
+        if grid.len() != GRID_SIZE * GRID_SIZE {
+            return Err(SudokuError::InvalidSize(grid.len()));
+        }
+
+        if let Some(&value) = grid.iter().find(|&&value| value > 9) {
+            return Err(SudokuError::InvalidValue(value));
+        }
+    }
 
     let mut image = RgbImage::from_pixel(IMAGE_SIZE, IMAGE_SIZE, WHITE_PIXEL);
 
+    let shown = puzzle
+        .iter()
+        .zip(attempt)
+        .map(|(&given, &value)| if given != 0 { given } else { value })
+        .collect::<Vec<_>>();
+
+    draw_grid(&mut image, &shown, |index| {
+        if puzzle[index] != 0 {
+            BLACK_PIXEL
+        } else if attempt[index] == solution[index] {
+            GREEN_PIXEL
+        } else {
+            RED_PIXEL
+        }
+    });
+
+    Ok(image)
+}
+
+/// Draws the grid lines and every filled cell's digit in the color 'digit_color' gives for its index
+fn draw_grid(image: &mut RgbImage, puzzle: &[u8], digit_color: impl Fn(usize) -> Rgb<u8>) {
     let font = FontRef::try_from_slice(ROBOTO_MEDIUM).expect("Font should be valid");
 
     for grid_pos in 0..(GRID_SIZE as u32) {
@@ -127,8 +418,8 @@ pub fn print_sudoku(puzzle: &[u8]) -> Result<RgbBuffer, SudokuError> {
         let y = (i / GRID_SIZE) * 100 + 5;
 
         draw_text_mut(
-            &mut image,
-            BLACK_PIXEL,
+            image,
+            digit_color(i),
             x as i32,
             y as i32,
             100.0,
@@ -136,15 +427,21 @@ pub fn print_sudoku(puzzle: &[u8]) -> Result<RgbBuffer, SudokuError> {
             &number.to_string(),
         );
     }
+}
 
-    Ok(image)
+/// Solves the puzzle and renders the solution
+pub fn print_sudoku_solved(puzzle: &[u8]) -> Result<RgbBuffer, SudokuError> {
+    print_sudoku(&solve_sudoku(puzzle)?)
 }
 
 #[cfg(test)]
 mod tests {
-    use std::io::Cursor;
+    use std::sync::atomic::AtomicBool;
 
-    use image::ImageFormat;
+    use crate::{
+        test_util::assert_png_snapshot,
+        util::{BLACK_PIXEL, GREEN_PIXEL, RED_PIXEL, WHITE_PIXEL},
+    };
 
     fn test_parse(string: &str, expected: Vec<u8>) {
         let actual = super::parse_sudoku(string).expect("should be ok");
@@ -156,13 +453,15 @@ mod tests {
         assert_eq!(acutal, expected);
     }
 
+    fn test_hint(puzzle: &[u8], solved: &[u8]) {
+        let (index, value, _) = super::sudoku_hint(puzzle).expect("should be ok");
+        assert_eq!(puzzle[index], 0);
+        assert_eq!(value, solved[index]);
+    }
+
     fn test_print(puzzle: Vec<u8>, expected: &[u8]) {
-        let mut actual = Vec::new();
-        super::print_sudoku(&puzzle)
-            .expect("should be ok")
-            .write_to(&mut Cursor::new(&mut actual), ImageFormat::Png)
-            .expect("should be ok");
-        assert_eq!(actual, expected);
+        let actual = super::print_sudoku(&puzzle).expect("should be ok");
+        assert_png_snapshot(&actual, expected);
     }
 
     //// GENERAL TESTS
@@ -190,17 +489,167 @@ mod tests {
         test_parse(EASY_STRING, EASY_UNSOLVED.to_vec());
     }
 
+    #[test]
+    fn parse_invalid_integer() {
+        let actual = super::parse_sudoku(&EASY_STRING.replacen('3', "é", 1).replacen('9', "x", 1))
+            .expect_err("should be Err");
+        assert!(matches!(
+            actual,
+            super::SudokuError::InvalidInteger { ch: 'é', index: 4 }
+        ));
+    }
+
+    #[test]
+    fn empty_cells_easy() {
+        assert_eq!(
+            super::sudoku_empty_cells(&EASY_UNSOLVED),
+            vec![
+                5, 6, 8, 9, 10, 12, 13, 16, 18, 19, 23, 24, 25, 28, 29, 33, 34, 35, 37, 38, 39, 40,
+                41, 46, 47, 48, 52, 53, 54, 55, 58, 59, 60, 65, 67, 68, 69, 71, 72, 73, 76, 79, 80
+            ]
+        );
+        assert!(super::sudoku_empty_cells(&EASY_SOLVED).is_empty());
+    }
+
+    #[test]
+    fn parse_grid_easy() {
+        const EASY_GRID: &str = "415|83.|.9.\n\
+                                 ..3|..9|1.4\n\
+                                 ..2|15.|..6\n\
+                                 ---+---+---\n\
+                                 9..|783|...\n\
+                                 2..|...|381\n\
+                                 5..|.12|4..\n\
+                                 ---+---+---\n\
+                                 ..4|9..|.63\n\
+                                 38.|5..|.4.\n\
+                                 ..9|3.7|5..";
+
+        let actual = super::parse_sudoku_grid(EASY_GRID).expect("should be ok");
+        assert_eq!(actual, EASY_UNSOLVED.to_vec());
+    }
+
     #[test]
     fn miri_solve_easy() {
         test_solve(&EASY_UNSOLVED, &EASY_SOLVED);
     }
 
+    #[test]
+    fn miri_solve_cancelled() {
+        let actual = super::solve_sudoku_cancellable(&EASY_UNSOLVED, &AtomicBool::new(true));
+        assert!(matches!(actual, Err(super::SudokuError::Cancelled)));
+    }
+
+    #[test]
+    fn hint_easy() {
+        test_hint(&EASY_UNSOLVED, &EASY_SOLVED);
+    }
+
+    #[test]
+    fn check_solution_easy() {
+        let check = |candidate: &[u8]| super::check_sudoku_solution(&EASY_UNSOLVED, candidate);
+
+        assert!(check(&EASY_SOLVED).expect("should be ok"));
+        assert!(!check(&EASY_UNSOLVED).expect("should be ok"));
+
+        let mut swapped = EASY_SOLVED;
+        swapped.swap(5, 6);
+        assert!(!check(&swapped).expect("should be ok"));
+
+        let mut changed_given = EASY_SOLVED;
+        changed_given.swap(0, 1);
+        assert!(!check(&changed_given).expect("should be ok"));
+
+        let actual = check(&EASY_SOLVED[1..]).expect_err("should be Err");
+        assert!(matches!(actual, super::SudokuError::InvalidSize(80)));
+    }
+
+    #[test]
+    fn complete_easy() {
+        let mut current = EASY_UNSOLVED;
+        current[6] = 2;
+        let actual = super::complete_sudoku(&current).expect("should be ok");
+        assert_eq!(actual, EASY_SOLVED);
+
+        // 7 is still a candidate for this cell, but leads nowhere
+        current[6] = 7;
+        let actual = super::complete_sudoku(&current).expect_err("should be Err");
+        assert!(matches!(actual, super::SudokuError::NoSolution));
+
+        current[6] = 0;
+        current[5] = 4;
+        let actual = super::complete_sudoku(&current).expect_err("should be Err");
+        assert!(matches!(actual, super::SudokuError::NoSolution));
+    }
+
+    #[test]
+    fn candidates_easy() {
+        let actual = super::sudoku_candidates(&EASY_UNSOLVED).expect("should be ok");
+        assert_eq!(actual[0], Vec::<u8>::new());
+        assert_eq!(actual[5], vec![6]);
+        assert_eq!(actual[6], vec![2, 7]);
+    }
+
+    #[test]
+    fn hint_easy_solved() {
+        let actual = super::sudoku_hint(&EASY_SOLVED).expect_err("should be Err");
+        assert!(matches!(actual, super::SudokuError::AlreadySolved));
+    }
+
     #[test]
     fn print_easy() {
         test_print(EASY_UNSOLVED.to_vec(), EASY_UNSOLVED_IMAGE);
         test_print(EASY_SOLVED.to_vec(), EASY_SOLVED_IMAGE);
     }
 
+    #[test]
+    fn print_highlighted() {
+        let image = super::print_sudoku_highlighted(&EASY_UNSOLVED, &[10], RED_PIXEL)
+            .expect("should be ok");
+        assert_eq!(*image.get_pixel(150, 150), RED_PIXEL);
+        assert_eq!(*image.get_pixel(50, 150), WHITE_PIXEL);
+        assert_eq!(*image.get_pixel(100, 150), BLACK_PIXEL);
+
+        let actual = super::print_sudoku_highlighted(&EASY_UNSOLVED, &[81], RED_PIXEL);
+        assert!(matches!(
+            actual,
+            Err(super::SudokuError::InvalidPosition { row: 9, col: 0 })
+        ));
+    }
+
+    #[test]
+    fn print_check() {
+        let empty = super::sudoku_empty_cells(&EASY_UNSOLVED);
+        let (right, wrong, blank) = (empty[0], empty[1], empty[2]);
+
+        let mut attempt = EASY_UNSOLVED;
+        attempt[right] = EASY_SOLVED[right];
+        attempt[wrong] = EASY_SOLVED[wrong] % 9 + 1;
+
+        let image = super::print_sudoku_check(&EASY_UNSOLVED, &attempt, &EASY_SOLVED)
+            .expect("should be ok");
+
+        let cell_contains = |index: usize, color| {
+            let (x, y) = ((index % 9) as u32 * 100, (index / 9) as u32 * 100);
+            (x + 3..x + 97).any(|px| (y + 3..y + 97).any(|py| *image.get_pixel(px, py) == color))
+        };
+
+        assert!(cell_contains(0, BLACK_PIXEL));
+        assert!(!cell_contains(0, GREEN_PIXEL) && !cell_contains(0, RED_PIXEL));
+        assert!(cell_contains(right, GREEN_PIXEL) && !cell_contains(right, RED_PIXEL));
+        assert!(cell_contains(wrong, RED_PIXEL) && !cell_contains(wrong, GREEN_PIXEL));
+        assert!(!cell_contains(blank, BLACK_PIXEL) && !cell_contains(blank, GREEN_PIXEL));
+
+        let actual = super::print_sudoku_check(&EASY_UNSOLVED, &attempt[1..], &EASY_SOLVED);
+        assert!(matches!(actual, Err(super::SudokuError::InvalidSize(80))));
+    }
+
+    #[test]
+    fn print_easy_solved() {
+        let actual = super::print_sudoku_solved(&EASY_UNSOLVED).expect("should be ok");
+        assert_png_snapshot(&actual, EASY_SOLVED_IMAGE);
+    }
+
     // Medium
     const MEDIUM_STRING: &str =
         "500000300009000027400105009200000070000006000006049000300027900080600000000034012";
@@ -230,6 +679,11 @@ mod tests {
         test_solve(&MEDIUM_UNSOLVED, &MEDIUM_SOLVED);
     }
 
+    #[test]
+    fn hint_medium() {
+        test_hint(&MEDIUM_UNSOLVED, &MEDIUM_SOLVED);
+    }
+
     #[test]
     fn print_medium() {
         test_print(MEDIUM_UNSOLVED.to_vec(), MEDIUM_UNSOLVED_IMAGE);
@@ -264,6 +718,25 @@ mod tests {
         test_solve(&HARD1_UNSOLVED, &HARD1_SOLVED);
     }
 
+    #[test]
+    fn hint_hard1() {
+        test_hint(&HARD1_UNSOLVED, &HARD1_SOLVED);
+    }
+
+    #[test]
+    fn hint_locked_candidates() {
+        // HARD1 once naked and hidden singles run out
+        let puzzle = super::parse_sudoku(
+            "100030400900400300304000072469005000800010600713600529000100700601050008040000010",
+        )
+        .expect("should be ok");
+
+        let (index, value, technique) = super::sudoku_hint(&puzzle).expect("should be ok");
+        assert_eq!(puzzle[index], 0);
+        assert_eq!(value, HARD1_SOLVED[index]);
+        assert_eq!(technique, super::Technique::LockedCandidates);
+    }
+
     #[test]
     fn print_hard1() {
         test_print(HARD1_UNSOLVED.to_vec(), HARD1_UNSOLVED_IMAGE);
@@ -298,6 +771,12 @@ mod tests {
         test_solve(&HARD2_UNSOLVED, &HARD2_SOLVED);
     }
 
+    #[test]
+    fn hint_hard2() {
+        let actual = super::sudoku_hint(&HARD2_UNSOLVED).expect_err("should be Err");
+        assert!(matches!(actual, super::SudokuError::NoHint));
+    }
+
     #[test]
     fn print_hard2() {
         test_print(HARD2_UNSOLVED.to_vec(), HARD2_UNSOLVED_IMAGE);
@@ -362,6 +841,33 @@ mod tests {
         assert!(matches!(actual, super::SudokuError::NoSolution));
     }
 
+    #[test]
+    #[cfg(feature = "rayon")]
+    fn solve_many() {
+        let puzzles = [EASY_STRING, IMPOSSIBLE_STRING, MEDIUM_STRING].map(String::from);
+        let actual = super::solve_many(&puzzles);
+
+        let expected = |solved: [u8; 81]| solved.iter().map(|x| x.to_string()).collect::<String>();
+        assert_eq!(
+            actual[0].as_ref().expect("should be ok"),
+            &expected(EASY_SOLVED)
+        );
+        assert!(matches!(actual[1], Err(super::SudokuError::NoSolution)));
+        assert_eq!(
+            actual[2].as_ref().expect("should be ok"),
+            &expected(MEDIUM_SOLVED)
+        );
+    }
+
+    #[test]
+    fn miri_solve_duplicate_given() {
+        let mut puzzle = EASY_UNSOLVED;
+        puzzle[5] = 4;
+
+        let actual = super::solve_sudoku(&puzzle).expect_err("should be Err");
+        assert!(matches!(actual, super::SudokuError::DuplicateGiven(5)));
+    }
+
     #[test]
     fn print_impossible() {
         test_print(IMPOSSIBLE_UNSOLVED.to_vec(), IMPOSSIBLE_UNSOLVED_IMAGE);
@@ -0,0 +1,116 @@
+use super::{DancingLinksError, DancingMatrix};
+
+/// Collects the rows of an exact cover problem before linking them into a `DancingMatrix`
+///
+/// ```
+/// use puzzle_utils::ExactCoverBuilder;
+///
+/// let mut builder = ExactCoverBuilder::new(3);
+/// builder.add_row(&[0, 1])?;
+/// builder.add_row(&[2])?;
+/// builder.add_row(&[1, 2])?;
+///
+/// let mut solution = builder.build().solve()?;
+/// solution.sort_unstable();
+///
+/// assert_eq!(solution, vec![0, 1]);
+/// # Ok::<(), puzzle_utils::DancingLinksError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct ExactCoverBuilder {
+    columns: Vec<Vec<usize>>,
+    num_rows: usize,
+}
+
+impl ExactCoverBuilder {
+    pub fn new(num_columns: usize) -> Self {
+        ExactCoverBuilder {
+            columns: vec![Vec::new(); num_columns],
+            num_rows: 0,
+        }
+    }
+
+    pub fn num_columns(&self) -> usize {
+        self.columns.len()
+    }
+
+    pub fn num_rows(&self) -> usize {
+        self.num_rows
+    }
+
+    /// Adds a row covering the given columns, returning its index in the solution
+    ///
+    /// ```
+    /// use puzzle_utils::{DancingLinksError, ExactCoverBuilder};
+    ///
+    /// let mut builder = ExactCoverBuilder::new(2);
+    ///
+    /// assert_eq!(builder.add_row(&[1]).ok(), Some(0));
+    /// assert!(matches!(builder.add_row(&[2]), Err(DancingLinksError::IndexError)));
+    /// assert!(matches!(builder.add_row(&[0, 0]), Err(DancingLinksError::DuplicateColumn)));
+    /// ```
+    pub fn add_row(&mut self, columns: &[usize]) -> Result<usize, DancingLinksError> {
+        for (i, &column) in columns.iter().enumerate() {
+            if column >= self.columns.len() {
+                return Err(DancingLinksError::IndexError);
+            }
+
+            if columns[..i].contains(&column) {
+                return Err(DancingLinksError::DuplicateColumn);
+            }
+        }
+
+        let row = self.num_rows;
+
+        for &column in columns {
+            self.columns[column].push(row);
+        }
+
+        self.num_rows += 1;
+
+        Ok(row)
+    }
+
+    pub fn build(&self) -> DancingMatrix {
+        DancingMatrix::new(self.columns.iter().map(|column| column.iter()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DancingLinksError, ExactCoverBuilder};
+
+    #[test]
+    fn miri_build() {
+        let mut builder = ExactCoverBuilder::new(7);
+
+        for row in [
+            vec![0, 3, 6],
+            vec![0, 3],
+            vec![3, 4, 6],
+            vec![2, 4, 5],
+            vec![1, 2, 5, 6],
+            vec![1, 6],
+        ] {
+            builder.add_row(&row).expect("should be Ok");
+        }
+
+        let mut solution = builder.build().solve().expect("should be Some");
+        solution.sort_unstable();
+
+        assert_eq!(solution, vec![1, 3, 5]);
+    }
+
+    #[test]
+    fn reject_invalid_rows() {
+        let mut builder = ExactCoverBuilder::new(3);
+
+        let actual = builder.add_row(&[0, 3]).expect_err("should be Err");
+        assert!(matches!(actual, DancingLinksError::IndexError));
+
+        let actual = builder.add_row(&[1, 2, 1]).expect_err("should be Err");
+        assert!(matches!(actual, DancingLinksError::DuplicateColumn));
+
+        assert_eq!(builder.num_rows(), 0);
+    }
+}
@@ -1,10 +1,18 @@
+mod builder;
 mod node;
 
-use std::{alloc::dealloc, mem::take, ptr::null_mut};
+use std::{
+    alloc::dealloc,
+    mem::take,
+    ptr::null_mut,
+    sync::atomic::{AtomicBool, Ordering},
+};
 
 use node::{Node, NODE_LAYOUT};
 use thiserror::Error;
 
+pub use builder::ExactCoverBuilder;
+
 #[derive(Debug, Error)]
 pub enum DancingLinksError {
     #[error("Index out of bounds")]
@@ -13,8 +21,13 @@ pub enum DancingLinksError {
     InvalidRow,
     #[error("No solution exists")]
     NoSolution,
+    #[error("Row covers the same column twice")]
+    DuplicateColumn,
+    #[error("Search was cancelled")]
+    Cancelled,
 }
 
+/// An exact cover problem linked for Algorithm X, built with `ExactCoverBuilder`
 pub struct DancingMatrix {
     root: *mut Node, // Points to a dummy column header
     rows: Vec<*mut Node>,
@@ -22,7 +35,11 @@ pub struct DancingMatrix {
 }
 
 impl DancingMatrix {
-    pub fn new<'a>(
+    /// Links each column's rows into the matrix, with no checks on the input
+    ///
+    /// A column must not list the same row twice, or covering that row frees its nodes twice on drop. Outside the
+    /// crate, matrices come from `ExactCoverBuilder::build`, which rejects such rows
+    pub(crate) fn new<'a>(
         constraints: impl Iterator<Item = impl ExactSizeIterator<Item = &'a usize>>,
     ) -> Self {
         let mut rows = Vec::new();
@@ -66,8 +83,11 @@ impl DancingMatrix {
             return Err(DancingLinksError::InvalidRow);
         }
 
-        for node in unsafe { Node::iter_down(Node::header(row)).skip(1) } {
-            self.rows[unsafe { Node::row(node) }] = null_mut();
+        // every row sharing a column with this one is freed below, so none of them can be added later
+        for col in unsafe { Node::iter_right(row) } {
+            for node in unsafe { Node::iter_down(Node::header(col)).skip(1) } {
+                self.rows[unsafe { Node::row(node) }] = null_mut();
+            }
         }
 
         for node in unsafe { Node::iter_right(row) } {
@@ -82,23 +102,32 @@ impl DancingMatrix {
         Ok(())
     }
 
-    pub fn solve(mut self) -> Result<Vec<usize>, DancingLinksError> {
-        if self.solve_helper() {
+    pub fn solve(self) -> Result<Vec<usize>, DancingLinksError> {
+        self.solve_cancellable(&AtomicBool::new(false))
+    }
+
+    /// Same as `solve`, but gives up with `Cancelled` once 'cancel' is set, which is checked before each branch
+    pub fn solve_cancellable(
+        mut self,
+        cancel: &AtomicBool,
+    ) -> Result<Vec<usize>, DancingLinksError> {
+        if self.solve_helper(cancel)? {
             Ok(take(&mut self.partial_solution))
         } else {
             Err(DancingLinksError::NoSolution)
         }
     }
 
-    fn solve_helper(&mut self) -> bool {
+    fn solve_helper(&mut self, cancel: &AtomicBool) -> Result<bool, DancingLinksError> {
         if self.is_empty() {
-            return true;
+            return Ok(true);
         }
 
-        let constraint = unsafe { Node::iter_right(self.root) }
-            .skip(1)
-            .min_by(|first, second| unsafe { Node::row(*first).cmp(&Node::row(*second)) })
-            .expect("Iterator should be non empty");
+        if cancel.load(Ordering::Relaxed) {
+            return Err(DancingLinksError::Cancelled);
+        }
+
+        let constraint = self.choose_constraint();
 
         unsafe { Node::cover_column(constraint) };
         for row in unsafe { Node::iter_down(constraint).skip(1) } {
@@ -108,14 +137,72 @@ impl DancingMatrix {
                 unsafe { Node::cover_column(node) };
             }
 
-            if self.solve_helper() {
+            let solved = self.solve_helper(cancel);
+
+            if matches!(solved, Ok(true)) {
                 for node in unsafe { Node::iter_right(row).skip(1) } {
                     unsafe { Node::free_chain(node) };
                 }
                 unsafe { Node::free_chain(constraint) };
 
-                return true;
+                return solved;
+            }
+
+            for node in unsafe { Node::iter_left(row).skip(1) } {
+                unsafe { Node::uncover_column(node) };
+            }
+
+            self.partial_solution.pop();
+
+            // Unwind with the matrix restored so Drop can still free it
+            if solved.is_err() {
+                unsafe { Node::uncover_column(constraint) };
+                return solved;
+            }
+        }
+        unsafe { Node::uncover_column(constraint) };
+
+        Ok(false)
+    }
+
+    /// Finds the solution using the fewest rows, searching the whole matrix rather than stopping at the first
+    pub fn solve_min(mut self) -> Option<Vec<usize>> {
+        let mut best = None;
+        self.solve_helper_min(&mut best);
+
+        best
+    }
+
+    fn solve_helper_min(&mut self, best: &mut Option<Vec<usize>>) {
+        if self.is_empty() {
+            if best
+                .as_ref()
+                .map_or(true, |best| self.partial_solution.len() < best.len())
+            {
+                *best = Some(self.partial_solution.clone());
             }
+            return;
+        }
+
+        // Any completion adds at least one more row, so it can't beat the best found
+        if best
+            .as_ref()
+            .is_some_and(|best| self.partial_solution.len() + 1 >= best.len())
+        {
+            return;
+        }
+
+        let constraint = self.choose_constraint();
+
+        unsafe { Node::cover_column(constraint) };
+        for row in unsafe { Node::iter_down(constraint).skip(1) } {
+            self.partial_solution.push(unsafe { Node::row(row) });
+
+            for node in unsafe { Node::iter_right(row).skip(1) } {
+                unsafe { Node::cover_column(node) };
+            }
+
+            self.solve_helper_min(best);
 
             for node in unsafe { Node::iter_left(row).skip(1) } {
                 unsafe { Node::uncover_column(node) };
@@ -124,8 +211,54 @@ impl DancingMatrix {
             self.partial_solution.pop();
         }
         unsafe { Node::uncover_column(constraint) };
+    }
 
-        false
+    /// Counts solutions, stopping once 'limit' have been found
+    pub fn count_solutions(&mut self, limit: usize) -> usize {
+        self.solve_helper_count(limit)
+    }
+
+    fn solve_helper_count(&mut self, limit: usize) -> usize {
+        if limit == 0 {
+            return 0;
+        }
+
+        if self.is_empty() {
+            return 1;
+        }
+
+        let constraint = self.choose_constraint();
+        let mut count = 0;
+
+        unsafe { Node::cover_column(constraint) };
+        for row in unsafe { Node::iter_down(constraint).skip(1) } {
+            for node in unsafe { Node::iter_right(row).skip(1) } {
+                unsafe { Node::cover_column(node) };
+            }
+
+            count += self.solve_helper_count(limit - count);
+
+            for node in unsafe { Node::iter_left(row).skip(1) } {
+                unsafe { Node::uncover_column(node) };
+            }
+
+            if count >= limit {
+                break;
+            }
+        }
+        unsafe { Node::uncover_column(constraint) };
+
+        count
+    }
+
+    /// Returns the header of the column with the fewest rows, breaking ties by the lowest column position
+    fn choose_constraint(&self) -> *mut Node {
+        unsafe { Node::iter_right(self.root) }
+            .skip(1)
+            .enumerate()
+            .min_by_key(|&(position, header)| (unsafe { Node::row(header) }, position))
+            .map(|(_, header)| header)
+            .expect("Iterator should be non empty")
     }
 
     fn is_empty(&self) -> bool {
@@ -145,6 +278,8 @@ impl Drop for DancingMatrix {
 
 #[cfg(test)]
 mod tests {
+    use std::sync::atomic::AtomicBool;
+
     #[test]
     fn miri_empty() {
         let constraints: [[usize; 0]; 0] = [];
@@ -217,4 +352,170 @@ mod tests {
 
         assert_eq!(solution, vec![1, 3, 5]);
     }
+
+    #[test]
+    fn miri_remove_conflicting_row() {
+        let constraints: [Vec<usize>; 2] = [vec![0, 1], vec![0, 2]];
+
+        let mut matrix =
+            super::DancingMatrix::new(constraints.iter().map(|constraint| constraint.iter()));
+        matrix.add_solution(0).expect("should be Ok");
+
+        let actual = matrix.add_solution(1).expect_err("should be Err");
+        assert!(matches!(actual, super::DancingLinksError::InvalidRow));
+
+        let actual = matrix.add_solution(2).expect_err("should be Err");
+        assert!(matches!(actual, super::DancingLinksError::InvalidRow));
+    }
+
+    #[test]
+    fn miri_drop_partial() {
+        let constraints: [Vec<usize>; 7] = [
+            vec![0, 1],
+            vec![4, 5],
+            vec![3, 4],
+            vec![0, 1, 2],
+            vec![2, 3],
+            vec![3, 4],
+            vec![0, 2, 4, 5],
+        ];
+
+        let mut matrix =
+            super::DancingMatrix::new(constraints.iter().map(|constraint| constraint.iter()));
+        matrix.add_solution(1).expect("should be Ok");
+        matrix.add_solution(3).expect("should be Ok");
+        drop(matrix);
+    }
+
+    #[test]
+    fn miri_drop_unsolved() {
+        let constraints: [Vec<usize>; 3] = [vec![0, 1], vec![1, 2], vec![0, 2]];
+
+        let matrix =
+            super::DancingMatrix::new(constraints.iter().map(|constraint| constraint.iter()));
+        let actual = matrix.solve().expect_err("should be Err");
+
+        assert!(matches!(actual, super::DancingLinksError::NoSolution));
+    }
+
+    #[test]
+    fn miri_solve_after_add_solution() {
+        let constraints: [Vec<usize>; 7] = [
+            vec![0, 1],
+            vec![4, 5],
+            vec![3, 4],
+            vec![0, 1, 2],
+            vec![2, 3],
+            vec![3, 4],
+            vec![0, 2, 4, 5],
+        ];
+
+        let mut matrix =
+            super::DancingMatrix::new(constraints.iter().map(|constraint| constraint.iter()));
+        matrix.add_solution(1).expect("should be Ok");
+        matrix.add_solution(5).expect("should be Ok");
+        let mut solution = matrix.solve().expect("should be Some");
+        solution.sort_unstable();
+
+        assert_eq!(solution, vec![1, 3, 5]);
+    }
+
+    #[test]
+    fn miri_count_solutions() {
+        let constraints: [Vec<usize>; 2] = [vec![0, 2], vec![1, 2]];
+
+        let mut matrix =
+            super::DancingMatrix::new(constraints.iter().map(|constraint| constraint.iter()));
+
+        assert_eq!(matrix.count_solutions(2), 2);
+        assert_eq!(matrix.count_solutions(1), 1);
+        assert_eq!(matrix.count_solutions(5), 2);
+        assert_eq!(matrix.count_solutions(0), 0);
+
+        let mut solution = matrix.solve().expect("should be Some");
+        solution.sort_unstable();
+
+        assert!(solution == vec![0, 1] || solution == vec![2]);
+    }
+
+    #[test]
+    fn miri_count_unique() {
+        let constraints: [Vec<usize>; 7] = [
+            vec![0, 1],
+            vec![4, 5],
+            vec![3, 4],
+            vec![0, 1, 2],
+            vec![2, 3],
+            vec![3, 4],
+            vec![0, 2, 4, 5],
+        ];
+
+        let mut matrix =
+            super::DancingMatrix::new(constraints.iter().map(|constraint| constraint.iter()));
+
+        assert_eq!(matrix.count_solutions(2), 1);
+    }
+
+    #[test]
+    fn miri_solve_tie_break() {
+        // Both columns have two rows, so the first column decides which solution is found. Choosing the last tied
+        // column instead would find the other solution in each case
+        for (constraints, expected) in [
+            ([vec![0, 1], vec![1, 2]], vec![0, 2]),
+            ([vec![1, 2], vec![0, 1]], vec![1]),
+        ] {
+            let matrix =
+                super::DancingMatrix::new(constraints.iter().map(|constraint| constraint.iter()));
+
+            let mut actual = matrix.solve().expect("should be ok");
+            actual.sort_unstable();
+            assert_eq!(actual, expected);
+        }
+    }
+
+    #[test]
+    fn miri_solve_min() {
+        let constraints: [Vec<usize>; 3] = [vec![0, 3], vec![1, 3], vec![2, 3]];
+
+        let matrix =
+            super::DancingMatrix::new(constraints.iter().map(|constraint| constraint.iter()));
+
+        assert_eq!(matrix.solve_min(), Some(vec![3]));
+    }
+
+    #[test]
+    fn miri_solve_min_after_add_solution() {
+        let constraints: [Vec<usize>; 3] = [vec![0, 3], vec![1, 3, 4], vec![2, 4]];
+
+        let mut matrix =
+            super::DancingMatrix::new(constraints.iter().map(|constraint| constraint.iter()));
+        matrix.add_solution(0).expect("should be ok");
+
+        let mut solution = matrix.solve_min().expect("should be Some");
+        solution.sort_unstable();
+
+        assert_eq!(solution, vec![0, 4]);
+    }
+
+    #[test]
+    fn miri_solve_min_none() {
+        let constraints: [Vec<usize>; 3] = [vec![0], vec![0, 1], vec![1]];
+
+        let matrix =
+            super::DancingMatrix::new(constraints.iter().map(|constraint| constraint.iter()));
+
+        assert_eq!(matrix.solve_min(), None);
+    }
+
+    #[test]
+    fn miri_cancelled() {
+        let constraints: [Vec<usize>; 2] = [vec![0, 2], vec![1, 2]];
+
+        let mut matrix =
+            super::DancingMatrix::new(constraints.iter().map(|constraint| constraint.iter()));
+        matrix.add_solution(0).expect("should be ok");
+
+        let actual = matrix.solve_cancellable(&AtomicBool::new(true));
+        assert!(matches!(actual, Err(super::DancingLinksError::Cancelled)));
+    }
 }
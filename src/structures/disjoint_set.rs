@@ -1,8 +1,10 @@
+/// A union-find over the indices `0..size`, with union by size and path compression
+#[derive(Debug, Clone, Default)]
 pub struct DisjointSet {
     elements: Vec<Element>,
 }
 
-#[derive(Clone)]
+#[derive(Debug, Clone)]
 struct Element {
     root: bool,
     value: usize,
@@ -25,24 +27,26 @@ impl Element {
 }
 
 impl DisjointSet {
-    #[allow(dead_code)]
+    /// Creates an empty set
     pub fn new() -> Self {
         DisjointSet {
             elements: Vec::new(),
         }
     }
 
+    /// Creates `size` elements, each in its own set
     pub fn with_size(size: usize) -> Self {
         DisjointSet {
             elements: vec![Element::new(); size],
         }
     }
 
-    #[allow(dead_code)]
+    /// Adds a new element in its own set, with the next index
     pub fn add(&mut self) {
         self.elements.push(Element::new());
     }
 
+    /// Returns the root of the set containing `idx`, or None if it's out of range
     pub fn find(&mut self, idx: usize) -> Option<usize> {
         if idx >= self.elements.len() {
             return None;
@@ -51,10 +55,19 @@ impl DisjointSet {
         Some(self.find_helper(idx))
     }
 
+    /// Points every element directly at its root, so later `find`s take a single step until the next `union`
+    pub fn compress_all(&mut self) {
+        for idx in 0..self.elements.len() {
+            self.find_helper(idx);
+        }
+    }
+
+    /// Returns whether both elements are in the same set, or None if either is out of range
     pub fn common_set(&mut self, idx_one: usize, idx_two: usize) -> Option<bool> {
         Some(self.find(idx_one)? == self.find(idx_two)?)
     }
 
+    /// Merges the sets containing both elements, returning the root that was absorbed, or the shared root if already merged
     pub fn union(&mut self, idx_one: usize, idx_two: usize) -> Option<usize> {
         let root_one = self.find(idx_one)?;
         let root_two = self.find(idx_two)?;
@@ -181,6 +194,24 @@ mod tests {
         assert_eq!(set.elements[4].value, 0);
     }
 
+    #[test]
+    fn compress_all() {
+        let mut set = super::DisjointSet::with_size(6);
+
+        set.union(0, 1);
+        set.union(2, 1);
+        set.union(3, 4);
+        set.union(4, 2);
+        set.compress_all();
+
+        for elem in &set.elements[1..5] {
+            assert!(!elem.root);
+            assert_eq!(elem.value, 0);
+        }
+        assert!(set.elements[0].root);
+        assert!(set.elements[5].root);
+    }
+
     #[test]
     fn common_set() {
         let mut set = super::DisjointSet::with_size(8);
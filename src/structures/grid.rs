@@ -0,0 +1,137 @@
+use std::ops::{Deref, DerefMut};
+
+/// A row-major 2D grid, dereferencing to its cells as a flat slice
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    data: Vec<T>,
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, value: T) -> Self {
+        Grid {
+            width,
+            height,
+            data: vec![value; width * height],
+        }
+    }
+
+    /// Returns a copy with rows and columns swapped, so columns can be read as contiguous slices
+    pub fn transposed(&self) -> Self {
+        let mut transposed = Grid {
+            width: self.height,
+            height: self.width,
+            data: self.data.clone(),
+        };
+        transposed.copy_transposed(self);
+
+        transposed
+    }
+
+    /// Overwrites this grid with the transpose of 'other', which must have swapped dimensions
+    pub fn copy_transposed(&mut self, other: &Grid<T>) {
+        debug_assert_eq!((self.width, self.height), (other.height, other.width));
+
+        for (y, row) in self.data.chunks_mut(self.width.max(1)).enumerate() {
+            for (x, cell) in row.iter_mut().enumerate() {
+                *cell = other.data[x * other.width + y].clone();
+            }
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    /// Wraps row-major cells, or returns None when there aren't exactly 'width * height' of them
+    pub fn from_vec(width: usize, height: usize, data: Vec<T>) -> Option<Self> {
+        (width.checked_mul(height) == Some(data.len())).then_some(Grid {
+            width,
+            height,
+            data,
+        })
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        self.offset(x, y).map(|offset| &self.data[offset])
+    }
+
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        self.offset(x, y).map(|offset| &mut self.data[offset])
+    }
+
+    /// The cells of row 'y', left to right
+    pub fn row_mut(&mut self, y: usize) -> &mut [T] {
+        &mut self.data[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn into_vec(self) -> Vec<T> {
+        self.data
+    }
+
+    fn offset(&self, x: usize, y: usize) -> Option<usize> {
+        (x < self.width && y < self.height).then_some(y * self.width + x)
+    }
+}
+
+impl<T> Deref for Grid<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        &self.data
+    }
+}
+
+impl<T> DerefMut for Grid<T> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        &mut self.data
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Grid;
+
+    #[test]
+    fn get() {
+        let mut grid = Grid::new(3, 2, 0);
+        *grid.get_mut(2, 1).expect("should be some") = 5;
+
+        assert_eq!(grid.get(2, 1), Some(&5));
+        assert_eq!(grid[5], 5);
+        assert_eq!(grid.get(3, 0), None);
+        assert_eq!(grid.get(0, 2), None);
+    }
+
+    #[test]
+    fn from_vec() {
+        let grid = Grid::from_vec(2, 2, vec![1, 2, 3, 4]).expect("should be some");
+
+        assert_eq!(grid.get(0, 1), Some(&3));
+        assert_eq!(Grid::from_vec(2, 2, vec![1, 2, 3]), None);
+    }
+
+    #[test]
+    fn rows() {
+        let mut grid = Grid::new(3, 2, 0);
+
+        for (i, cell) in grid.row_mut(1).iter_mut().enumerate() {
+            *cell = i + 1;
+        }
+
+        assert_eq!(grid.into_vec(), vec![0, 0, 0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn transposed() {
+        let mut grid = Grid::new(3, 2, 0);
+        grid.copy_from_slice(&[1, 2, 3, 4, 5, 6]);
+
+        let mut transposed = grid.transposed();
+        assert_eq!(transposed.row_mut(2), &[3, 6]);
+        assert_eq!(&*transposed, &[1, 4, 2, 5, 3, 6]);
+
+        transposed.row_mut(0)[1] = 7;
+        grid.copy_transposed(&transposed);
+        assert_eq!(grid.into_vec(), vec![1, 2, 3, 7, 5, 6]);
+    }
+}
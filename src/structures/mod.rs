@@ -1,2 +1,3 @@
 pub mod dancing_links;
 pub mod disjoint_set;
+pub mod grid;
@@ -0,0 +1,112 @@
+use std::{env, fs, thread};
+
+use image::{
+    codecs::png::{CompressionType, FilterType, PngEncoder},
+    ImageFormat,
+};
+
+use crate::RgbBuffer;
+
+/// Asserts that two images are identical, reporting the first differing pixel and how many pixels differ
+pub fn assert_images_eq(actual: &RgbBuffer, expected: &RgbBuffer) {
+    assert_eq!(
+        actual.dimensions(),
+        expected.dimensions(),
+        "image dimensions differ"
+    );
+
+    let mut differing = actual
+        .enumerate_pixels()
+        .zip(expected.pixels())
+        .filter(|((_, _, actual), expected)| actual != expected);
+
+    if let Some(((x, y, actual_pixel), expected_pixel)) = differing.next() {
+        panic!(
+            "images differ at ({x}, {y}): {:?} != {:?} ({} differing pixels)",
+            actual_pixel.0,
+            expected_pixel.0,
+            differing.count() + 1
+        );
+    }
+}
+
+/// Encodes an image as a PNG with a fixed compression level and filter, so snapshots don't depend on the
+/// `image` crate's encoder defaults
+pub fn encode_png(image: &RgbBuffer) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    image
+        .write_with_encoder(PngEncoder::new_with_quality(
+            &mut bytes,
+            CompressionType::Best,
+            FilterType::NoFilter,
+        ))
+        .expect("image should encode");
+
+    bytes
+}
+
+/// Asserts that an image matches a PNG snapshot, comparing decoded pixels so the snapshot's encoding doesn't matter
+///
+/// On a mismatch the actual image is written with `encode_png` to the temp directory, named after the test, so it
+/// can replace the snapshot if the change was intended
+pub fn assert_png_snapshot(actual: &RgbBuffer, expected: &[u8]) {
+    let expected = image::load_from_memory_with_format(expected, ImageFormat::Png)
+        .expect("snapshot should decode")
+        .to_rgb8();
+
+    if *actual != expected {
+        let name = thread::current()
+            .name()
+            .unwrap_or("snapshot")
+            .replace("::", "-");
+        let path = env::temp_dir().join(format!("{name}.png"));
+        fs::write(&path, encode_png(actual)).expect("snapshot should write");
+        eprintln!("actual image written to {}", path.display());
+    }
+
+    assert_images_eq(actual, &expected);
+}
+
+#[cfg(test)]
+mod tests {
+    use image::ImageBuffer;
+
+    use crate::util::{BLACK_PIXEL, WHITE_PIXEL};
+
+    use super::{assert_images_eq, assert_png_snapshot, encode_png};
+
+    #[test]
+    fn equal_images() {
+        let image = ImageBuffer::from_pixel(3, 2, WHITE_PIXEL);
+        assert_images_eq(&image, &image.clone());
+    }
+
+    #[test]
+    fn encoded_snapshot() {
+        let mut image = ImageBuffer::from_pixel(3, 2, WHITE_PIXEL);
+        image.put_pixel(1, 0, BLACK_PIXEL);
+
+        assert_eq!(encode_png(&image), encode_png(&image.clone()));
+        assert_png_snapshot(&image, &encode_png(&image));
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "images differ at (1, 0): [0, 0, 0] != [255, 255, 255] (2 differing pixels)"
+    )]
+    fn differing_pixels() {
+        let expected = ImageBuffer::from_pixel(3, 2, WHITE_PIXEL);
+        let mut actual = expected.clone();
+        actual.put_pixel(1, 0, BLACK_PIXEL);
+        actual.put_pixel(2, 1, BLACK_PIXEL);
+        assert_images_eq(&actual, &expected);
+    }
+
+    #[test]
+    #[should_panic(expected = "image dimensions differ")]
+    fn differing_dimensions() {
+        let expected = ImageBuffer::from_pixel(3, 2, WHITE_PIXEL);
+        let actual = ImageBuffer::from_pixel(2, 3, WHITE_PIXEL);
+        assert_images_eq(&actual, &expected);
+    }
+}
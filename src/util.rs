@@ -1,32 +1,180 @@
 use image::Rgb;
-use rand::{seq::IteratorRandom, thread_rng};
+use rand::{seq::IteratorRandom, Rng};
+
+use crate::RgbBuffer;
 
 pub const WHITE_PIXEL: Rgb<u8> = Rgb([255, 255, 255]);
 pub const BLACK_PIXEL: Rgb<u8> = Rgb([0, 0, 0]);
 pub const RED_PIXEL: Rgb<u8> = Rgb([255, 0, 0]);
+pub const GREEN_PIXEL: Rgb<u8> = Rgb([0, 160, 0]);
 pub const GRAY_PIXEL: Rgb<u8> = Rgb([105, 105, 105]);
+pub const LIGHT_GRAY_PIXEL: Rgb<u8> = Rgb([192, 192, 192]);
 
 pub const ROBOTO_MEDIUM: &[u8] = include_bytes!("../resources/Roboto-Medium.ttf");
 
-pub fn choose_random<T>(vec: &mut Vec<T>) -> Option<T> {
-    let idx = (0..vec.len()).choose(&mut thread_rng())?;
+pub fn choose_random<T>(vec: &mut Vec<T>, rng: &mut (impl Rng + ?Sized)) -> Option<T> {
+    let idx = (0..vec.len()).choose(rng)?;
     Some(vec.swap_remove(idx))
 }
 
+/// Removes a random element, chosen with probability proportional to its weight
+///
+/// Falls back to a uniform choice if no element has a positive weight
+pub fn choose_weighted<T>(
+    vec: &mut Vec<T>,
+    weight: impl Fn(&T) -> f32,
+    rng: &mut (impl Rng + ?Sized),
+) -> Option<T> {
+    let weight = |elem: &T| weight(elem).max(0.0);
+    let total = vec.iter().map(weight).sum::<f32>();
+
+    if total <= 0.0 {
+        return choose_random(vec, rng);
+    }
+
+    let mut target = rng.gen_range(0.0..total);
+    let idx = vec
+        .iter()
+        .position(|elem| {
+            target -= weight(elem);
+            target < 0.0
+        })
+        .unwrap_or(vec.len() - 1);
+
+    Some(vec.swap_remove(idx))
+}
+
+/// Downsamples an image to a `target_w` by `target_h` grid, marking cells whose average luminance is below `threshold`
+///
+/// Each cell averages the block of pixels it covers, so the image size doesn't need to divide evenly
+pub fn image_to_bool_grid(
+    image: &RgbBuffer,
+    threshold: u8,
+    target_w: usize,
+    target_h: usize,
+) -> Vec<bool> {
+    let width = image.width() as usize;
+    let height = image.height() as usize;
+
+    if width == 0 || height == 0 {
+        return vec![false; target_w * target_h];
+    }
+
+    let span = |index: usize, target: usize, size: usize| {
+        let start = (index * size / target).min(size - 1);
+        let end = ((index + 1) * size / target).clamp(start + 1, size);
+        start..end
+    };
+
+    (0..target_w * target_h)
+        .map(|i| {
+            let xs = span(i % target_w, target_w, width);
+            let ys = span(i / target_w, target_h, height);
+            let count = (xs.len() * ys.len()) as u64;
+
+            let total = ys
+                .flat_map(|y| xs.clone().map(move |x| (x, y)))
+                .map(|(x, y)| {
+                    let Rgb([r, g, b]) = *image.get_pixel(x as u32, y as u32);
+                    299 * r as u64 + 587 * g as u64 + 114 * b as u64
+                })
+                .sum::<u64>();
+
+            total / (count * 1000) < threshold as u64
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod choose_random_tests {
+    use rand::thread_rng;
+
     use super::*;
 
     #[test]
     fn removes_element_from_vec() {
         let mut vec = vec![1, 2, 3, 4, 5];
-        let elem = choose_random(&mut vec).expect("element should be chosen");
+        let elem = choose_random(&mut vec, &mut thread_rng()).expect("element should be chosen");
         assert!(!vec.contains(&elem));
     }
 
     #[test]
     fn returns_none_if_empty() {
         let mut vec = Vec::<i32>::new();
-        assert!(choose_random(&mut vec).is_none());
+        assert!(choose_random(&mut vec, &mut thread_rng()).is_none());
+    }
+}
+
+#[cfg(test)]
+mod choose_weighted_tests {
+    use rand::thread_rng;
+
+    use super::*;
+
+    #[test]
+    fn skips_zero_weights() {
+        for _ in 0..20 {
+            let mut vec = vec![1, 2, 3];
+            let elem = choose_weighted(
+                &mut vec,
+                |&elem| if elem == 2 { 1.0 } else { 0.0 },
+                &mut thread_rng(),
+            )
+            .expect("element should be chosen");
+            assert_eq!(elem, 2);
+            assert_eq!(vec.len(), 2);
+        }
+    }
+
+    #[test]
+    fn falls_back_to_uniform() {
+        let mut vec = vec![1, 2, 3];
+        let elem = choose_weighted(&mut vec, |_| 0.0, &mut thread_rng())
+            .expect("element should be chosen");
+        assert!(!vec.contains(&elem));
+    }
+
+    #[test]
+    fn returns_none_if_empty() {
+        let mut vec = Vec::<i32>::new();
+        assert!(choose_weighted(&mut vec, |_| 1.0, &mut thread_rng()).is_none());
+    }
+}
+
+#[cfg(test)]
+mod image_to_bool_grid_tests {
+    use image::ImageBuffer;
+
+    use super::*;
+
+    #[test]
+    fn thresholds_blocks() {
+        let image = ImageBuffer::from_fn(4, 4, |x, y| {
+            if x < 2 && y < 2 {
+                BLACK_PIXEL
+            } else {
+                WHITE_PIXEL
+            }
+        });
+
+        let actual = image_to_bool_grid(&image, 128, 2, 2);
+        assert_eq!(actual, vec![true, false, false, false]);
+    }
+
+    #[test]
+    fn averages_uneven_blocks() {
+        let image =
+            ImageBuffer::from_fn(3, 1, |x, _| if x == 0 { BLACK_PIXEL } else { WHITE_PIXEL });
+
+        let actual = image_to_bool_grid(&image, 128, 2, 1);
+        assert_eq!(actual, vec![true, false]);
+    }
+
+    #[test]
+    fn upsamples_small_images() {
+        let image = ImageBuffer::from_pixel(1, 1, BLACK_PIXEL);
+
+        let actual = image_to_bool_grid(&image, 128, 3, 2);
+        assert_eq!(actual, vec![true; 6]);
     }
 }
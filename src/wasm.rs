@@ -0,0 +1,74 @@
+use wasm_bindgen::prelude::{wasm_bindgen, JsError};
+
+use crate::{
+    create_maze, create_maze_with_ends, image_to_png_bytes, nonogram_to_ascii, parse_nonogram,
+    parse_sudoku, print_maze, print_maze_solved, print_nonogram, print_nonogram_solved,
+    print_sudoku, print_sudoku_solved, solve_nonogram, solve_sudoku_string, MazeAlgorithm,
+    NonogramPuzzle,
+};
+
+/// Solves a sudoku given as 81 digits (`0` for an empty space), returning the solution in the same format
+#[wasm_bindgen]
+pub fn sudoku_solution(puzzle: &str) -> Result<String, JsError> {
+    Ok(solve_sudoku_string(puzzle)?)
+}
+
+/// Renders a sudoku given as 81 digits to PNG bytes, solving it first if `solved` is set
+#[wasm_bindgen]
+pub fn sudoku_png(puzzle: &str, solved: bool) -> Result<Vec<u8>, JsError> {
+    let puzzle = parse_sudoku(puzzle)?;
+
+    let image = if solved {
+        print_sudoku_solved(&puzzle)?
+    } else {
+        print_sudoku(&puzzle)?
+    };
+
+    Ok(image_to_png_bytes(&image)?)
+}
+
+/// Solves a nonogram given as `;`-separated rules of `,`-separated runs, returning the rules and solved grid as text
+/// with `#` for filled cells and `.` for empty ones
+#[wasm_bindgen]
+pub fn nonogram_solution(col: &str, row: &str) -> Result<String, JsError> {
+    let NonogramPuzzle { col, row, .. } = parse_nonogram(col, row)?;
+    let solution = solve_nonogram(&col, &row)?;
+
+    Ok(nonogram_to_ascii(&col, &row, Some(&solution)))
+}
+
+/// Renders a nonogram to PNG bytes, solving it first if `solved` is set
+#[wasm_bindgen]
+pub fn nonogram_png(col: &str, row: &str, solved: bool) -> Result<Vec<u8>, JsError> {
+    let NonogramPuzzle { col, row, .. } = parse_nonogram(col, row)?;
+
+    let image = if solved {
+        print_nonogram_solved(&col, &row, &solve_nonogram(&col, &row)?)?
+    } else {
+        print_nonogram(col.len() as u32, row.len() as u32, &col, &row)?
+    };
+
+    Ok(image_to_png_bytes(&image)?)
+}
+
+/// Generates a maze and renders it to PNG bytes, drawing the solution if `solved` is set
+#[wasm_bindgen]
+pub fn maze_png(width: usize, height: usize, solved: bool) -> Result<Vec<u8>, JsError> {
+    let (grid, solution) = create_maze(width, height, MazeAlgorithm::RecursiveBacktrack)?;
+
+    let image = if solved {
+        print_maze_solved(width, height, &grid, &solution)?
+    } else {
+        print_maze(width, height, &grid)?
+    };
+
+    Ok(image_to_png_bytes(&image)?)
+}
+
+/// Generates a maze, returning it as JSON along with its solution and the cells the solution joins
+#[wasm_bindgen]
+pub fn maze_json(width: usize, height: usize) -> Result<String, JsError> {
+    let maze = create_maze_with_ends(width, height, MazeAlgorithm::RecursiveBacktrack)?;
+
+    Ok(serde_json::to_string(&maze)?)
+}